@@ -0,0 +1,14 @@
+use std::{env, process::Command};
+
+fn main() {
+    // Benchmark history records which compiler built the solutions it timed
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"));
+    let version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map_or(String::from("unknown"), |version| version.trim().to_string());
+    println!("cargo:rustc-env=AOC_RUSTC_VERSION={version}");
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -0,0 +1,139 @@
+use std::{alloc::{GlobalAlloc, Layout, System}, cell::Cell, fmt, sync::atomic::{AtomicBool, Ordering}};
+
+use serde::{Deserialize, Serialize};
+
+/// Whether any part is being tracked, so the allocator can skip the thread-local otherwise
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+#[derive(Clone, Copy, Default)]
+struct Counters {
+    tracking: bool,
+    count: u64,
+    bytes: u64,
+    /// Bytes allocated minus bytes freed since tracking started. Freeing memory allocated
+    /// before then can take it below zero.
+    live: i64,
+    peak: i64,
+}
+
+thread_local! {
+    static COUNTERS: Cell<Counters> = const { Cell::new(Counters { tracking: false, count: 0, bytes: 0, live: 0, peak: 0 }) };
+}
+
+/// The system allocator, counting what a part allocates while [`track`] is running it
+///
+/// Counts are kept per thread, which covers a whole part since none of them start threads of
+/// their own. A `realloc` counts as a new allocation of the new size.
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn record(allocated: usize, freed: usize) {
+        if !ENABLED.load(Ordering::Relaxed) {
+            return;
+        }
+        // Threads that are shutting down have no counters left, and don't run parts anyway
+        let _ = COUNTERS.try_with(|counters| {
+            let mut c = counters.get();
+            if c.tracking {
+                if allocated > 0 {
+                    c.count += 1;
+                    c.bytes += allocated as u64;
+                }
+                c.live += allocated as i64 - freed as i64;
+                c.peak = c.peak.max(c.live);
+                counters.set(c);
+            }
+        });
+    }
+}
+
+// SAFETY: every call is passed straight on to `System`, and counting never allocates
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            CountingAlloc::record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            CountingAlloc::record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CountingAlloc::record(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            CountingAlloc::record(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// How much one part allocated
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllocStats {
+    /// Number of allocations, reallocations included
+    pub count: u64,
+    /// Bytes asked for across every allocation
+    pub bytes: u64,
+    /// The most bytes the part had allocated at once
+    pub peak: u64,
+}
+
+impl fmt::Display for AllocStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} allocations, {} total, {} peak", self.count, Bytes(self.bytes), Bytes(self.peak))
+    }
+}
+
+/// A byte count in the largest binary unit that keeps it at least 1
+struct Bytes(u64);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+        if self.0 < 1024 {
+            return write!(f, "{} B", self.0);
+        }
+        let mut size = self.0 as f64 / 1024.0;
+        let mut unit = 0;
+        while size >= 1024.0 && unit + 1 < UNITS.len() {
+            size /= 1024.0;
+            unit += 1;
+        }
+        write!(f, "{size:.1} {}", UNITS[unit])
+    }
+}
+
+/// Turns on allocation tracking for every part run from now on
+///
+/// Only does anything when [`CountingAlloc`] is the global allocator.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Runs `f`, also returning what it allocated on this thread when tracking is enabled
+pub fn track<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !enabled() {
+        return (f(), None);
+    }
+    let outer = COUNTERS.replace(Counters { tracking: true, ..Counters::default() });
+    let result = f();
+    let counters = COUNTERS.replace(outer);
+    let stats = AllocStats { count: counters.count, bytes: counters.bytes, peak: counters.peak.max(0) as u64 };
+    (result, Some(stats))
+}
@@ -0,0 +1,67 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// The answer to one part of a puzzle
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Answer {
+    Int(i128),
+    Str(String),
+    /// Multi-line text, like the letters drawn by day 10
+    Grid(String),
+}
+
+impl Answer {
+    /// Builds a grid answer from its rows
+    pub fn grid<I, S>(rows: I) -> Answer
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let rows = rows.into_iter().map(|row| row.as_ref().to_string()).collect::<Vec<_>>();
+        Answer::Grid(rows.join("\n"))
+    }
+
+    /// The answer squeezed onto one line, for tables
+    pub fn one_line(&self) -> String {
+        match self {
+            Answer::Grid(s) => format!("<{} line grid>", s.lines().count()),
+            _ => self.to_string(),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::Str(s) | Answer::Grid(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Answer {
+                fn from(n: $ty) -> Answer {
+                    Answer::Int(i128::try_from(n).expect("answer doesn't fit in an i128"))
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Str(s)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Str(s.to_string())
+    }
+}
@@ -0,0 +1,158 @@
+use std::{collections::BTreeMap, fmt::Display, fs, io, path::PathBuf};
+
+use colored::{ColoredString, Colorize};
+use serde::Serialize;
+
+use crate::{answer::Answer, error::{AocError, Result}, solution::Part};
+
+/// The confirmed answers for one year, stored in `answers/<year>.toml`
+///
+/// Each day is a table keyed by its number, holding `part1` and `part2`:
+///
+/// ```toml
+/// [1]
+/// part1 = 24000
+/// part2 = 45000
+/// ```
+#[derive(Debug)]
+pub struct AnswerFile {
+    pub year: u32,
+    days: BTreeMap<u32, BTreeMap<Part, Answer>>,
+}
+
+impl AnswerFile {
+    pub fn path(year: u32) -> PathBuf {
+        PathBuf::from(format!("./answers/{year}.toml"))
+    }
+
+    /// Reads the answers for `year`, starting empty if there's no file yet
+    pub fn load(year: u32) -> io::Result<AnswerFile> {
+        let mut file = AnswerFile { year, days: BTreeMap::new() };
+        let text = match fs::read_to_string(AnswerFile::path(year)) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(file),
+            Err(e) => return Err(e),
+        };
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData,
+            format!("{}: {msg}", AnswerFile::path(year).display()));
+
+        let table = text.parse::<toml::Table>().map_err(|e| invalid(e.to_string()))?;
+        for (day, parts) in table {
+            let day = day.parse().map_err(|_| invalid(format!("{day:?} isn't a day")))?;
+            let parts = parts.as_table().ok_or_else(|| invalid(format!("day {day} isn't a table")))?;
+            for (key, value) in parts {
+                let part = match key.as_str() {
+                    "part1" => Part::One,
+                    "part2" => Part::Two,
+                    _ => return Err(invalid(format!("day {day} has an unknown key {key:?}"))),
+                };
+                let answer = answer_from_toml(value)
+                    .ok_or_else(|| invalid(format!("day {day} part {part} isn't a number or string")))?;
+                file.insert(day, part, answer);
+            }
+        }
+        Ok(file)
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<&Answer> {
+        self.days.get(&day)?.get(&part)
+    }
+
+    pub fn insert(&mut self, day: u32, part: Part, answer: Answer) {
+        self.days.entry(day).or_default().insert(part, answer);
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let mut table = toml::Table::new();
+        for (day, parts) in &self.days {
+            let mut day_table = toml::Table::new();
+            for (part, answer) in parts {
+                let value = match answer {
+                    Answer::Int(n) => match i64::try_from(*n) {
+                        Ok(n) => toml::Value::Integer(n),
+                        Err(_) => toml::Value::String(n.to_string()),
+                    },
+                    Answer::Str(s) | Answer::Grid(s) => toml::Value::String(s.clone()),
+                };
+                day_table.insert(format!("part{part}"), value);
+            }
+            table.insert(day.to_string(), toml::Value::Table(day_table));
+        }
+
+        let path = AnswerFile::path(self.year);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let text = toml::to_string(&table).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, text)
+    }
+}
+
+/// Reads an answer stored as a TOML integer or string, with multi-line strings as grids
+pub(crate) fn answer_from_toml(value: &toml::Value) -> Option<Answer> {
+    match value {
+        toml::Value::Integer(n) => Some(Answer::Int(*n as i128)),
+        toml::Value::String(s) if s.contains('\n') => Some(Answer::Grid(s.clone())),
+        toml::Value::String(s) => Some(Answer::Str(s.clone())),
+        _ => None,
+    }
+}
+
+/// How an answer compares to the stored one
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Pass,
+    #[serde(rename = "fail")]
+    Mismatch,
+    /// Nothing is stored for this part yet
+    Missing,
+    /// The part returned an error instead of an answer
+    #[serde(rename = "error")]
+    Failed,
+    /// The part ran past its time limit
+    Timeout,
+    /// The part's process went over its memory or CPU limit
+    #[serde(rename = "limit")]
+    OverLimit,
+}
+
+impl Verdict {
+    /// Answers are compared by their text, so `"24000"` in the file matches `24000`
+    pub fn check(expected: Option<&Answer>, actual: &Result<Answer>) -> Verdict {
+        match (expected, actual) {
+            (_, Err(AocError::Timeout { .. })) => Verdict::Timeout,
+            (_, Err(AocError::LimitExceeded { .. })) => Verdict::OverLimit,
+            (_, Err(_)) => Verdict::Failed,
+            (Some(expected), Ok(actual)) if expected.to_string() == actual.to_string() => Verdict::Pass,
+            (Some(_), Ok(_)) => Verdict::Mismatch,
+            (None, Ok(_)) => Verdict::Missing,
+        }
+    }
+
+    /// The verdict padded to a fixed width and colored, for tables
+    pub fn colored(self) -> ColoredString {
+        let s = format!("{self:<8}");
+        match self {
+            Verdict::Pass => s.green(),
+            Verdict::Mismatch => s.red(),
+            Verdict::Missing => s.yellow(),
+            Verdict::Failed => s.red(),
+            Verdict::Timeout | Verdict::OverLimit => s.magenta(),
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Verdict::Pass => "PASS",
+            Verdict::Mismatch => "MISMATCH",
+            Verdict::Missing => "MISSING",
+            Verdict::Failed => "FAILED",
+            Verdict::Timeout => "TIMEOUT",
+            Verdict::OverLimit => "LIMIT",
+        };
+        f.pad(s)
+    }
+}
@@ -1,47 +1,40 @@
-use crate::utils::*;
-use std::time::Instant;
-use colored::Colorize;
+use crate::{answer::Answer, error::{AocError, Result}, parse::Lines, solution::{Parsed, Solution}};
 
-pub fn part1(input: &str) {
-    let mut cals = split_by_big_gap(input);
+const DAY: u32 = 1;
 
-    let cals = cals.iter_mut().map(|s| {
-        let mut total = 0;
-        for st in s.split_ascii_whitespace() {
-            total += st.parse::<i32>().unwrap();
-        }
-        total
-    });
-    println!("{}", cals.max().unwrap());
+/// The calories each elf is carrying, in input order
+pub fn parse(input: &str) -> Result<Vec<i32>> {
+    Lines::blocks(DAY, input)
+        .into_iter()
+        .map(|block| block.map(|line| line.parse(|c| c.int::<i32>())).sum())
+        .collect()
 }
 
-pub fn part2(input: &str) {
-    let mut cals = split_by_big_gap(input);
+pub fn part1(cals: &[i32]) -> Result<Answer> {
+    let max = cals.iter().copied().max().ok_or_else(|| AocError::no_answer(DAY, "there are no elves"))?;
+    Ok(max.into())
+}
 
-    let mut cals = cals.iter_mut().map(|s| {
-        let mut total = 0;
-        for st in s.split_ascii_whitespace() {
-            total += st.parse::<i32>().unwrap();
-        }
-        total
-    }).collect::<Vec<i32>>();
+pub fn part2(cals: &[i32]) -> Result<Answer> {
+    let mut cals = cals.to_vec();
+    if cals.len() < 3 {
+        return Err(AocError::no_answer(DAY, "there are fewer than three elves"));
+    }
     let mut true_tot = 0;
     for _ in 0..3 {
         let max = cals.iter().max().unwrap();
         true_tot += max;
         cals.remove(cals.iter().position(|num| num == max).unwrap());
     }
-    println!("{true_tot}");
+    Ok(true_tot.into())
 }
 
-pub fn day1(input: &str) {
-    println!("{}", "Day 1:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Vec<i32>>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<Vec<i32>>()) }
 }
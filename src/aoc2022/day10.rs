@@ -1,34 +1,47 @@
-use std::time::Instant;
-use colored::Colorize;
+use crate::{answer::Answer, error::Result, parse::Lines, solution::{Parsed, Solution}};
 
-fn loop_lines<F>(input: &str, mut f: F)
+const DAY: u32 = 10;
+
+/// The program, with `None` for a `noop` and the amount for an `addx`
+pub fn parse(input: &str) -> Result<Vec<Option<i32>>> {
+    Lines::new(DAY, input)
+        .map(|line| line.parse(|c| {
+            if c.optional("noop") {
+                return Ok(None);
+            }
+            c.tag("addx ")?;
+            Ok(Some(c.int()?))
+        }))
+        .collect()
+}
+
+fn loop_lines<F>(program: &[Option<i32>], mut f: F)
 where
     F: FnMut(i32, i32) -> bool,
 {
     let mut x = 1;
     let mut cycle = 1;
-    for line in input.split("\n") {
+    for &instruction in program {
         if f(cycle, x) {
             break;
         }
-        if line == "noop" {
-            cycle += 1;
-        }
-        else {
-            let add: i32 = line.split_ascii_whitespace().skip(1).next().unwrap().parse().unwrap();
-            cycle += 1;
-            if f(cycle, x) {
-                break;
+        match instruction {
+            None => cycle += 1,
+            Some(add) => {
+                cycle += 1;
+                if f(cycle, x) {
+                    break;
+                }
+                x += add;
+                cycle += 1;
             }
-            x += add;
-            cycle += 1;
         }
     }
 }
 
-pub fn part1(input: &str) {
+pub fn part1(program: &[Option<i32>]) -> Result<Answer> {
     let mut sum = 0;
-    loop_lines(input, |cycle, x| {
+    loop_lines(program, |cycle, x| {
         if cycle > 220 {
             return true;
         }
@@ -37,33 +50,34 @@ pub fn part1(input: &str) {
         } 
         false
     });
-    println!("{sum}");
+    Ok(sum.into())
 }
 
-pub fn part2(input: &str) {
-    loop_lines(input, |cycle, x| {
+pub fn part2(program: &[Option<i32>]) -> Result<Answer> {
+    let mut rows = Vec::new();
+    let mut row = String::new();
+    loop_lines(program, |cycle, x| {
         let pos = cycle - 1;
         if (pos % 40 as i32).abs_diff(x) < 2 {
-            print!("#");
+            row.push('#');
         }
         else {
-            print!(".");
+            row.push('.');
         }
         if cycle % 40 == 0 {
-            println!();
+            rows.push(std::mem::take(&mut row));
         }
         false
-    });    
+    });
+    Ok(Answer::grid(rows))
 }
 
-pub fn day10(input: &str) {
-    println!("{}", "Day 10:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
+pub struct Day10;
+
+impl Solution for Day10 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Vec<Option<i32>>>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<Vec<Option<i32>>>()) }
+}
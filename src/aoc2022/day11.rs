@@ -1,77 +1,98 @@
-use std::time::Instant;
-use colored::Colorize;
-struct Monkey {
+use crate::{answer::Answer, error::{AocError, Result}, parse::Lines, solution::{Parsed, Solution}};
+
+const DAY: u32 = 11;
+
+pub struct Monkey {
     pub items: Vec<u128>,
-    pub change: Box<dyn Fn(&mut u128)>,
+    pub change: Box<dyn Fn(&mut u128) + Send + Sync>,
     pub test: u128,
     pub throw_idxs: (usize, usize),
 }
 
 impl Monkey {
-    pub fn new(s: &str) -> Monkey {
-        let it = s.split("\n");
-        let mut it = it.skip(1);
-        let mut starting = it.next().unwrap();
-        starting = &starting[18..];
-        let starting = starting.split(", ");
-        let mut items = Vec::new();
-        for line in starting {
-            items.push(line.parse().unwrap());
-        }
-        let mut operation = it.next().unwrap();
-
-        let divisible = it.next().unwrap();
-        let divisible = divisible[21..].parse().unwrap();
-
-        operation = &operation[23..];
-        let change= if &operation[0..1] == "+" {
-            if &operation[2..] == "old" {
-                Box::new(|x: &mut u128| *x += *x) as Box<dyn Fn(&mut u128)>
-            } else {
-                let num: u128 = operation[2..].parse().unwrap();
-                Box::new(move |x: &mut u128| *x += num) as Box<dyn Fn(&mut u128)>
+    pub fn new(mut lines: Lines) -> Result<Monkey> {
+        lines.expect("a monkey")?.parse(|c| {
+            c.tag("Monkey ")?;
+            c.int::<usize>()?;
+            c.tag(":")
+        })?;
+        let items = lines.expect("the starting items")?.parse(|c| {
+            c.tag("  Starting items: ")?;
+            if c.is_empty() {
+                return Ok(Vec::new());
             }
-        } else {
-            if &operation[2..] == "old" {
-                Box::new(|x: &mut u128| *x *= *x) as Box<dyn Fn(&mut u128)>
+            c.list(", ", |c| c.int())
+        })?;
+
+        let change = lines.expect("the operation")?.parse(|c| {
+            c.tag("  Operation: new = old ")?;
+            let op = c.one_of("+*")?;
+            c.tag(" ")?;
+            let change = if c.optional("old") {
+                if op == '+' {
+                    Box::new(|x: &mut u128| *x += *x) as Box<dyn Fn(&mut u128) + Send + Sync>
+                } else {
+                    Box::new(|x: &mut u128| *x *= *x) as Box<dyn Fn(&mut u128) + Send + Sync>
+                }
             } else {
-                let num: u128 = operation[2..].parse().unwrap();
-                Box::new(move |x: &mut u128| *x *= num) as Box<dyn Fn(&mut u128)>
+                let num: u128 = c.int()?;
+                if op == '+' {
+                    Box::new(move |x: &mut u128| *x += num) as Box<dyn Fn(&mut u128) + Send + Sync>
+                } else {
+                    Box::new(move |x: &mut u128| *x *= num) as Box<dyn Fn(&mut u128) + Send + Sync>
+                }
+            };
+            Ok(change)
+        })?;
+
+        let divisible = lines.expect("the test")?.parse(|c| {
+            c.tag("  Test: divisible by ")?;
+            let start = c.clone();
+            match c.int()? {
+                0 => Err(start.error("a number above 0")),
+                n => Ok(n),
             }
-        };
-        
-        let on_true = it.next().unwrap();
-        let on_true = on_true[29..].parse().unwrap();
-        let on_false = it.next().unwrap();
-        let on_false = on_false[30..].parse().unwrap();
-
-        Monkey { items, change,test: divisible, throw_idxs: (on_true, on_false) }
+        })?;
+        let on_true = lines.expect("where to throw when the test passes")?.parse(|c| {
+            c.tag("    If true: throw to monkey ")?;
+            c.int()
+        })?;
+        let on_false = lines.expect("where to throw when the test fails")?.parse(|c| {
+            c.tag("    If false: throw to monkey ")?;
+            c.int()
+        })?;
+        if let Some(line) = lines.next() {
+            return Err(line.error_at(1, "a blank line"));
+        }
+
+        Ok(Monkey { items, change, test: divisible, throw_idxs: (on_true, on_false) })
     }
 }
 
-fn parse_lines(input: &str) -> Vec<String> {
-    let mut v = Vec::new();
-    let mut s = String::new();
-    for line in input.split("\n") {
-        if line.trim().len() == 0 {
-            v.push(s);
-            s = String::new();
-        }
-        else {
-            s.push_str(line);
-            s.push_str("\n");
+pub fn parse(input: &str) -> Result<Vec<Monkey>> {
+    let monkeys = Lines::blocks(DAY, input).into_iter().map(Monkey::new).collect::<Result<Vec<_>>>()?;
+    if monkeys.len() < 2 {
+        return Err(AocError::no_answer(DAY, "there need to be at least two monkeys"));
+    }
+    for (idx, monkey) in monkeys.iter().enumerate() {
+        let (on_true, on_false) = monkey.throw_idxs;
+        if on_true.max(on_false) >= monkeys.len() {
+            return Err(AocError::no_answer(DAY, format!("monkey {idx} throws to a monkey that isn't there")));
         }
     }
-    v.push(s);
-    v
+    Ok(monkeys)
+}
+
+/// The items each monkey starts with, which the parts move around without changing the monkeys
+fn starting_items(monkeys: &[Monkey]) -> Vec<Vec<u128>> {
+    monkeys.iter().map(|monkey| monkey.items.clone()).collect()
 }
 
-pub fn part1(input: &str) {
-    let mut monkeys = Vec::new();
+pub fn part1(monkeys: &[Monkey]) -> Result<Answer> {
+    let mut items = starting_items(monkeys);
     let mut max_possible = 1;
-    for monkey in parse_lines(input) {
-        monkeys.push(Monkey::new(monkey.as_str()));
-        max_possible *= monkeys.last().unwrap().test;
+    for monkey in monkeys {
+        max_possible *= monkey.test;
     }
     
     let mut inspects = vec![0; monkeys.len()];
@@ -79,20 +100,20 @@ pub fn part1(input: &str) {
     
     for _round in 0..20 {
         for monkey_idx in 0..monkeys.len() {
-            while monkeys[monkey_idx].items.len() > 0 {
-                let monkey = &mut monkeys[monkey_idx];
+            while !items[monkey_idx].is_empty() {
+                let monkey = &monkeys[monkey_idx];
                 let divis = monkey.test;
                 let (true_idx, false_idx) = monkey.throw_idxs;
-                let mut item = monkey.items.pop().unwrap();
+                let mut item = items[monkey_idx].pop().unwrap();
                 (monkey.change)(&mut item);
                 inspects[monkey_idx] += 1;
                 item /= 3;
                 item %= max_possible;
 
                 if item % divis == 0 {
-                    monkeys[true_idx].items.push(item);
+                    items[true_idx].push(item);
                 } else {
-                    monkeys[false_idx].items.push(item);
+                    items[false_idx].push(item);
                 }
                 
             }
@@ -101,34 +122,33 @@ pub fn part1(input: &str) {
 
     inspects.sort();
     inspects.reverse();
-    println!("{}", inspects[0] * inspects[1]);
+    Ok((inspects[0] * inspects[1]).into())
 }
 
-pub fn part2(input: &str) {
-    let mut monkeys = Vec::new();
+pub fn part2(monkeys: &[Monkey]) -> Result<Answer> {
+    let mut items = starting_items(monkeys);
     let mut max_possible = 1;
-    for monkey in parse_lines(input) {
-        monkeys.push(Monkey::new(monkey.as_str()));
-        max_possible *= monkeys.last().unwrap().test;
+    for monkey in monkeys {
+        max_possible *= monkey.test;
     }
 
     let mut inspects = vec![0_u128; monkeys.len()];
     for _round in 0..10000 {
         for monkey_idx in 0..monkeys.len() {
-            while monkeys[monkey_idx].items.len() > 0 {
-                let monkey = &mut monkeys[monkey_idx];
+            while !items[monkey_idx].is_empty() {
+                let monkey = &monkeys[monkey_idx];
                 let divis = monkey.test;
                 let (true_idx, false_idx) = monkey.throw_idxs;
-                let mut item = monkey.items.pop().unwrap();
+                let mut item = items[monkey_idx].pop().unwrap();
 
                 (monkey.change)(&mut item);
                 inspects[monkey_idx] += 1;
 
                 item %= max_possible;
                 if item % divis == 0_u128 {
-                    monkeys[true_idx].items.push(item);
+                    items[true_idx].push(item);
                 } else {
-                    monkeys[false_idx].items.push(item);
+                    items[false_idx].push(item);
                 }
                 
             }
@@ -136,17 +156,15 @@ pub fn part2(input: &str) {
     }
     inspects.sort();
     inspects.reverse();
-    println!("{}", inspects[0] * inspects[1]);
+    Ok((inspects[0] * inspects[1]).into())
 }
 
-pub fn day11(input: &str) {
-    println!("{}", "Day 11:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
+pub struct Day11;
+
+impl Solution for Day11 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Vec<Monkey>>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<Vec<Monkey>>()) }
+}
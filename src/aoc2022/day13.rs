@@ -1,61 +1,27 @@
-use std::time::Instant;
-use colored::Colorize;
+use crate::{answer::Answer, error::Result, parse::{Cursor, Lines}, solution::{Parsed, Solution}};
 
-fn parse_lines(input: &str) -> Vec<String> {
-    let mut v = Vec::new();
-    let mut s = String::new();
-    for line in input.split("\n") {
-        if line.trim().len() == 0 {
-            v.push(s);
-            s = String::new();
-        }
-        else {
-            s.push_str(line);
-            s.push_str("\n");
-        }
-    }
-    v.push(s);
-    v
-}
+const DAY: u32 = 13;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-enum Value {
+pub enum Value {
     List(Vec<Value>),
     Integer(u8)
 }
 
-fn parse_list(mut line: &str) -> Vec<Value> {
-    let mut vals = Vec::new();
-    while !line.is_empty() {
-        vals.push(
-            if &line[0..1] == "[" {
-                let mut level = 1;
-                let mut idx = 1;
-                while idx < line.len() && !(&line[idx.. idx + 1] == "," && level == 0) {
-                    if &line[idx..idx + 1] == "[" {
-                        level += 1;
-                    } 
-                    else if &line[idx..idx + 1] == "]" {
-                        level -= 1;
-                    }
-                    idx += 1;
-                }
-                let new_val = parse_list(&line[1..idx - 1]);
-                let min = if line.len() < idx + 1 { line.len() } else { idx + 1 };
-                line = &line[min .. line.len()]; // skip comma
-                Value::List(new_val)
-            } 
-            else {
-                let end_idx = line.find(",").or(Some(line.len())).unwrap();
-                let num = line[0..end_idx].parse().unwrap();
-
-                let min = if line.len() < end_idx + 1 { line.len() } else { end_idx + 1 };
-                line = &line[min..line.len()]; // Skip comma
-                Value::Integer(num)
-            }
-        );
+/// Reads a value: an integer, or a bracketed list of values separated by commas
+fn parse_value(c: &mut Cursor) -> Result<Value> {
+    if c.optional("[") {
+        let mut vals = Vec::new();
+        if !c.optional("]") {
+            vals = c.list(",", parse_value)?;
+            c.tag("]")?;
+        }
+        Ok(Value::List(vals))
+    } else if c.rest().starts_with(|c: char| c.is_ascii_digit()) {
+        Ok(Value::Integer(c.int()?))
+    } else {
+        Err(c.error("a number or a list"))
     }
-    vals
 }
 
 impl PartialOrd for Value {
@@ -108,14 +74,21 @@ impl Ord for Value {
     }
 }
 
-pub fn part1(input: &str) {
+/// The packets, in the pairs they're listed in
+pub fn parse(input: &str) -> Result<Vec<(Value, Value)>> {
     let mut packets = Vec::new();
-    for lines in parse_lines(input){
-        let [line1, line2] = lines.split("\n").next_chunk().unwrap();
-        let line1 = &line1[1..line1.len() - 1];
-        let line2 = &line2[1..line2.len() - 1];
-        packets.push((Value::List(parse_list(line1)), Value::List(parse_list(line2))));
+    for mut lines in Lines::blocks(DAY, input) {
+        let pack1 = lines.expect("a packet")?.parse(parse_value)?;
+        let pack2 = lines.expect("a second packet")?.parse(parse_value)?;
+        if let Some(line) = lines.next() {
+            return Err(line.error_at(1, "a blank line"));
+        }
+        packets.push((pack1, pack2));
     }
+    Ok(packets)
+}
+
+pub fn part1(packets: &[(Value, Value)]) -> Result<Answer> {
     let mut sum = 0;
     for idx in 0..packets.len() {
         let (pack1, pack2) = &packets[idx];
@@ -123,16 +96,11 @@ pub fn part1(input: &str) {
             sum += idx + 1
         }
     }
-    println!("{}", sum);
+    Ok(sum.into())
 }
 
-pub fn part2(input: &str) {
-    let mut packets = Vec::new();
-    for line in input.split("\n") {
-        if !line.trim().is_empty() {
-            packets.push(Value::List(parse_list(line)));
-        }
-    }
+pub fn part2(pairs: &[(Value, Value)]) -> Result<Answer> {
+    let mut packets = pairs.iter().flat_map(|(pack1, pack2)| [pack1.clone(), pack2.clone()]).collect::<Vec<_>>();
     // divider packets
     let div_pkt_1 = Value::List(vec![Value::List(vec![Value::Integer(2)])]);
     let div_pkt_2 = Value::List(vec![Value::List(vec![Value::Integer(6)])]);
@@ -143,17 +111,15 @@ pub fn part2(input: &str) {
     let div_idx_1 = packets.iter().position(|v| *v == div_pkt_1).unwrap() + 1;
     let div_idx_2 = packets.iter().position(|v| *v == div_pkt_2).unwrap() + 1;
 
-    println!("{}", div_idx_1 * div_idx_2);
+    Ok((div_idx_1 * div_idx_2).into())
 }
 
-pub fn day13(input: &str) {
-    println!("{}", "Day 13:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
+pub struct Day13;
+
+impl Solution for Day13 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Vec<(Value, Value)>>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<Vec<(Value, Value)>>()) }
+}
@@ -1,239 +1,106 @@
-use std::time::Instant;
-use colored::Colorize;
+use crate::{answer::Answer, error::Result, parse::{Line, Lines}, solution::{Parsed, Solution}, utils::grid::{Grid, Pos}};
 
-#[derive(PartialEq, Eq)]
-enum FallResult {
-    Moved,
-    MovedAndStopped,
-    FellIntoVoid
-}
+const DAY: u32 = 14;
 
-fn sim_sand_fall(map: &mut Vec<Vec<char>>, sand_idxs: (usize, usize)) -> (FallResult, Option<usize>) {
-    let (row, col) = sand_idxs;
-    // First, try to move down
-    let new_row;
-    if map[row].len() <= col + 1 {
-        return (FallResult::FellIntoVoid, None);
-    }
-    if map[row][col + 1] == '.' {
-        map[row][col] = '.';
-        map[row][col + 1] = 'S';
-        new_row = row;
-    } 
-    else {
-        if row != 0 && map[row - 1].len() <= col + 1 {
-            return (FallResult::FellIntoVoid, None);
-        }
-        if row != 0 && map[row - 1][col + 1] == '.' {
-            map[row][col] = '.';
-            map[row - 1][col + 1] = 'S';
-            new_row = row - 1;
-        }
-        else {
-            if map[row + 1].len() <= col + 1 {
-                return (FallResult::FellIntoVoid, None);
-            }
-            map[row][col] = '.';
-            map[row + 1][col + 1] = 'S';
-            new_row = row + 1;
-        }
-    }
-    
-    if map[new_row].len() <= col + 2 {
-        return (FallResult::FellIntoVoid, None);
-    } 
-    else if map[new_row][col + 2] == '#' || map[new_row][col + 2] == 'O' {
-        if new_row != 0 && map[new_row - 1].len() <= col + 2 {
-            return (FallResult::FellIntoVoid, None);
-        }
-        else if new_row == 0 || (map[new_row - 1][col + 2] == '#' || map[new_row - 1][col + 2] == 'O') {
-            if new_row < map.len() - 1 && map[new_row + 1].len() <= col + 2 {
-                return (FallResult::FellIntoVoid, None);
-            }
-            else if new_row >= map.len() - 1 || (map[new_row + 1][col + 2] == '#' || map[new_row + 1][col + 2] == 'O')  {
-                map[new_row][col + 1] = 'O';
-                (FallResult::MovedAndStopped, Some(new_row))
-            }
-            else {
-                (FallResult::Moved, Some(new_row))
-            }
-        }
-        else {
-            (FallResult::Moved, Some(new_row))
-        }
-    }
-    else {
-        (FallResult::Moved, Some(new_row))
-    }
-}
+/// Where the sand pours in from
+const SOURCE_X: usize = 500;
 
-fn add_map_floor(map: &mut Vec<Vec<char>>) {
-    let floor_col_idx = max_map_depth(map) + 1;
-    for row_idx in 0..map.len() {
-        ensure_coord_writable(map, row_idx, floor_col_idx);
-        map[row_idx][floor_col_idx] = '#';
-    }
+/// Reads a path of `x,y` points joined by ` -> `, each in a straight line from the last
+fn parse_path(line: Line) -> Result<Vec<(usize, usize)>> {
+    let mut last: Option<(usize, usize)> = None;
+    line.parse(|c| c.list(" -> ", |c| {
+        let start = c.clone();
+        let x = c.int()?;
+        c.tag(",")?;
+        let y = c.int()?;
+        if last.is_some_and(|(lastx, lasty)| lastx != x && lasty != y) {
+            return Err(start.error("a point in a straight line from the last one"));
+        }
+        last = Some((x, y));
+        Ok((x, y))
+    }))
 }
 
-fn ensure_coord_writable(map: &mut Vec<Vec<char>>, row: usize, col: usize) {
-    if map.len() <= row {
-        for _ in map.len()..row + 1 {
-            map.push(Vec::new());
-        }
-    }
-    if map[row].len() <= col {
-        for _ in map[row].len()..col + 1 {
-            map[row].push('.');
-        }
-    }
+/// A slice of the cave with the rock paths drawn in, reaching down to the row above the
+/// floor and wide enough for the sand to pile up against the floor either side of the source
+#[derive(Clone)]
+pub struct Cave {
+    map: Grid<char>,
+    /// The x coordinate of the map's first column
+    left: usize,
 }
 
-fn parse_line(line: &str, map: &mut Vec<Vec<char>>) {
-    let coords = line.split(" -> ");
-    let mut coords = coords.map(|coords| {
-        let mut coords = coords.split(",");
-        let x = coords.next().unwrap().parse().unwrap();
-        let y = coords.next().unwrap().parse().unwrap();
-        (x, y)
-    });
-    let start = coords.next().unwrap();
-    let mut last = start;
-    for pair in coords {
-        //println!("{:?}", pair);
-        let (lastx, lasty) = last;
-        let (x, y) = pair;
-        if y == lasty {
-            let mut range = lastx..x + 1;
-            if x < lastx {
-                range = x..lastx + 1;
-            }
-
-            for xcoord in range {
-                ensure_coord_writable(map, xcoord, y);
-                //println!("Writing to {},{}", xcoord, y);
-                map[xcoord][y] = '#';
-            }
-        } else {
-            let mut range = lasty..y + 1;
-            if y < lasty {
-                range = y..lasty + 1;
-            }
+pub fn parse(input: &str) -> Result<Cave> {
+    let paths = Lines::new(DAY, input).map(parse_path).collect::<Result<Vec<_>>>()?;
+    let points = || paths.iter().flatten();
+    let depth = points().map(|&(_, y)| y).max().unwrap_or(0) + 2;
+    let left = points().map(|&(x, _)| x).min().unwrap_or(SOURCE_X).min(SOURCE_X.saturating_sub(depth));
+    let right = points().map(|&(x, _)| x).max().unwrap_or(SOURCE_X).max(SOURCE_X + depth);
 
-            for ycoord in range {
-                ensure_coord_writable(map, x, ycoord);
-                //println!("Writing to {},{}", x, ycoord);
-                map[x][ycoord] = '#';
+    let mut map = Grid::new(depth, right - left + 1, '.');
+    for path in &paths {
+        for pair in path.windows(2) {
+            let ((x1, y1), (x2, y2)) = (pair[0], pair[1]);
+            for y in y1.min(y2)..=y1.max(y2) {
+                for x in x1.min(x2)..=x1.max(x2) {
+                    map[(y, x - left)] = '#';
+                }
             }
         }
-        last = pair;
-    } 
-} 
-
-fn max_map_depth(map: &Vec<Vec<char>>) -> usize {
-    let mut max = 0;
-    for row in map {
-        if row.len() > max {
-            max = row.len();
-        }
     }
-    max
+    Ok(Cave { map, left })
 }
 
-fn _print_map(map: &Vec<Vec<char>>) {
-    for col_idx in 0..max_map_depth(map) {
-        for row in map {
-            // Check should skip
-            let mut should_skip = true;
-            for c in &row[0..row.len() - 1] {
-                should_skip = should_skip && *c == '.';
-            }
-
-            if col_idx >= row.len() {
-                continue;
+/// Drops a grain of sand from the source, giving where it comes to rest
+///
+/// Without a floor, sand that falls past the lowest rock keeps going forever, and `None` is
+/// given instead.
+fn drop_sand(cave: &mut Cave, floor: bool) -> Option<Pos> {
+    let (mut row, mut col) = (0, SOURCE_X - cave.left);
+    'falling: loop {
+        if row + 1 == cave.map.height() {
+            if !floor {
+                return None;
             }
-            if !should_skip {
-                print!(" {} ", row[col_idx]);
+            break;
+        }
+        for next in [col, col - 1, col + 1] {
+            if cave.map[(row + 1, next)] == '.' {
+                (row, col) = (row + 1, next);
+                continue 'falling;
             }
         }
-        println!();
+        break;
     }
+    cave.map[(row, col)] = 'o';
+    Some((row, col))
 }
 
-pub fn part1(input: &str) {
-    let mut map = Vec::new();
-    for line in input.split("\n") {
-        parse_line(line, &mut map);
-    }
-    ensure_coord_writable(&mut map, 500, 0);
-    map[500][0] = '+';
-    //print_map(&map);
+pub fn part1(cave: &Cave) -> Result<Answer> {
+    let mut cave = cave.clone();
     let mut to_rest = 0;
-    let mut sand_idxs = (500, 1);
-    loop {
-        let (res, new_row) = sim_sand_fall(&mut map, sand_idxs);
-        if res == FallResult::FellIntoVoid {
-            break;
-        }
-        else if res == FallResult::MovedAndStopped {
-            to_rest += 1;
-            sand_idxs = (500, 1);
-        }
-        else {
-            sand_idxs = (new_row.unwrap(), sand_idxs.1 + 1);
-        }
+    while drop_sand(&mut cave, false).is_some() {
+        to_rest += 1;
     }
-    println!("{}", to_rest);
+    Ok(to_rest.into())
 }
 
-pub fn part2(input: &str) {
-    let mut map = Vec::new();
-    for line in input.split("\n") {
-        parse_line(line, &mut map);
-    }
-    // Continue floor rightwards
-    let max_depth = max_map_depth(&map);
-    for _ in 0..max_depth {
-        let max_row = map.len();
-        for col in 0..max_depth {
-            ensure_coord_writable(&mut map, max_row, col);
-        }
+pub fn part2(cave: &Cave) -> Result<Answer> {
+    let mut cave = cave.clone();
+    let source = (0, SOURCE_X - cave.left);
+    let mut to_rest = 1;
+    while drop_sand(&mut cave, true) != Some(source) {
+        to_rest += 1;
     }
-    add_map_floor(&mut map);
-    ensure_coord_writable(&mut map, 500, 0);
-    map[500][0] = '+';
-    let mut to_rest = 0;
-    let mut sand_idxs = (500, 0);
-    loop {
-        let (res, new_row) = sim_sand_fall(&mut map, sand_idxs);
-        if res == FallResult::MovedAndStopped {
-            to_rest += 1;
-            sand_idxs = (500, 0);
-        }
-        else {
-            sand_idxs = (new_row.unwrap(), sand_idxs.1 + 1);
-        }
-        if map[500][0] == '.' {
-            if map[500][1] == 'O' && map[499][1] == 'O' && map[501][1] == 'O' {
-                break;
-            }
-            else {
-                map[500][0] = '+';
-            }
-        } 
-        
-    }
-    println!("{}", to_rest + 1);
-   
+    Ok(to_rest.into())
 }
 
-pub fn day14(input: &str) {
-    println!("{}", "Day 14:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
+pub struct Day14;
+
+impl Solution for Day14 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Cave>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<Cave>()) }
+}
@@ -1,7 +1,13 @@
-use std::time::Instant;
-use colored::Colorize;
+use crate::{answer::Answer, error::{AocError, Result}, params::{Overrides, ParamSet}, parse::{Cursor, Lines}, solution::{Parsed, Part, Solution}, utils::Point2};
 
-const COLUMN_AT: isize = 2000000;
+const DAY: u32 = 15;
+
+crate::params! {
+    /// The row part 1 counts the ruled-out positions in (10 in the example)
+    column_at: isize = 2000000,
+    /// How far from 0 the distress beacon can be on either axis (20 in the example)
+    range: isize = 4000000,
+}
 
 #[repr(u8)]
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
@@ -12,22 +18,34 @@ enum Item {
     Unknown,
 }
 
-fn coords_to_idxs(coords: &str) -> (isize, isize) {
-    let x = &coords[2..coords.find(",").unwrap()];
-    let y = &coords[coords.rfind("=").unwrap() + 1..];
-    (x.parse().unwrap(), y.parse().unwrap())
-}
+/// A sensor's position and the position of the beacon closest to it
+type Reading = (Point2<isize>, Point2<isize>);
+
+/// Reads each `Sensor at x=.., y=..: closest beacon is at x=.., y=..` line into a sensor and its beacon
+pub fn parse(input: &str) -> Result<Vec<Reading>> {
+    fn coords(c: &mut Cursor) -> Result<Point2<isize>> {
+        c.tag("x=")?;
+        let x = c.int()?;
+        c.tag(", y=")?;
+        let y = c.int()?;
+        Ok(Point2::new(x, y))
+    }
 
-fn manhattan_distance(sensor: (isize, isize), beacon: (isize, isize)) -> usize {
-    sensor.0.abs_diff(beacon.0) + sensor.1.abs_diff(beacon.1)
+    Lines::new(DAY, input).map(|line| line.parse(|c| {
+        c.tag("Sensor at ")?;
+        let sensor = coords(c)?;
+        c.tag(": closest beacon is at ")?;
+        let beacon = coords(c)?;
+        Ok((sensor, beacon))
+    })).collect()
 }
 
-fn all_coords_with_mh_dis_add(sensor: (isize, isize), beacon: (isize, isize), 
-    dis: usize, col: &mut Vec<Item>, min_x: isize, column_at: isize) {
-    fn insert(col: &mut Vec<Item>, coord: (isize, isize), min_x: isize, 
-        sensor: (isize, isize), beacon: (isize, isize), column_at: isize) {
-        if coord.1 == column_at && coord.0 - min_x >= 0 && ((coord.0 - min_x) as usize) < col.len() {
-            col[(coord.0 - min_x) as usize] = if coord == sensor {
+fn all_coords_with_mh_dis_add(sensor: Point2<isize>, beacon: Point2<isize>, 
+    dis: usize, col: &mut [Item], min_x: isize, column_at: isize) {
+    fn insert(col: &mut [Item], coord: Point2<isize>, min_x: isize, 
+        sensor: Point2<isize>, beacon: Point2<isize>, column_at: isize) {
+        if coord.y == column_at && coord.x - min_x >= 0 && ((coord.x - min_x) as usize) < col.len() {
+            col[(coord.x - min_x) as usize] = if coord == sensor {
                 Item::Sensor
             }
             else if coord == beacon {
@@ -39,68 +57,61 @@ fn all_coords_with_mh_dis_add(sensor: (isize, isize), beacon: (isize, isize),
         }
     }
 
-    //println!("Sensor at {sensor:?}, dis: {dis}");
     let dis = dis as isize;
     for y_offset in 0..dis+1 {
-        if sensor.1 + y_offset != column_at && sensor.1 - y_offset != column_at {
+        if sensor.y + y_offset != column_at && sensor.y - y_offset != column_at {
             continue;
         }
 
         for x_offset in 0..(dis - y_offset) + 1 {
-            let coord = (sensor.0 + x_offset, sensor.1 + y_offset);
+            let coord = sensor + Point2::new(x_offset, y_offset);
             insert(col, coord, min_x, sensor, beacon, column_at);
             
-            let coord = (sensor.0 + x_offset, sensor.1 - y_offset);
+            let coord = sensor + Point2::new(x_offset, -y_offset);
             insert(col, coord, min_x, sensor, beacon, column_at);
 
-            let coord = (sensor.0 - x_offset, sensor.1 + y_offset);
+            let coord = sensor + Point2::new(-x_offset, y_offset);
             insert(col, coord, min_x, sensor, beacon, column_at);
 
-            let coord = (sensor.0 - x_offset, sensor.1 - y_offset);
+            let coord = sensor + Point2::new(-x_offset, -y_offset);
             insert(col, coord, min_x, sensor, beacon, column_at);
         }
     }
 }
 
-pub fn part1(input: &str) {
+pub fn part1(sensors: &[Reading]) -> Result<Answer> {
+    part1_with(sensors, &Params::default())
+}
+
+pub fn part1_with(sensors: &[Reading], params: &Params) -> Result<Answer> {
+    let column_at = params.column_at;
     let mut min_y = isize::MAX;
     let mut min_x = isize::MAX;
     let mut max_y = isize::MIN;
     let mut max_x = isize::MIN;
-    for line in input.split("\n"){  
-        let mut sensor_coords = &line[10..];
-        sensor_coords = &sensor_coords[0..sensor_coords.find(":").unwrap()];
-        let beacon_coords = &line[line.rfind("x=").unwrap()..];
-        let sensor_coords = coords_to_idxs(sensor_coords);
-        let beacon_coords = coords_to_idxs(beacon_coords);
-        let dis = manhattan_distance(sensor_coords, beacon_coords) as isize;
-        if sensor_coords.0 - dis < min_x {
-            min_x = sensor_coords.0 - dis;
+    for &(sensor_coords, beacon_coords) in sensors {
+        let dis = sensor_coords.manhattan(beacon_coords) as isize;
+        if sensor_coords.x - dis < min_x {
+            min_x = sensor_coords.x - dis;
         }
-        if sensor_coords.0 + dis > max_x {
-            max_x = sensor_coords.0 + dis;
+        if sensor_coords.x + dis > max_x {
+            max_x = sensor_coords.x + dis;
         }
-        if sensor_coords.1 - dis < min_y {
-            min_y = sensor_coords.1 - dis;
+        if sensor_coords.y - dis < min_y {
+            min_y = sensor_coords.y - dis;
         }
-        if sensor_coords.1 + dis > max_y {
-            max_y = sensor_coords.1 + dis;
+        if sensor_coords.y + dis > max_y {
+            max_y = sensor_coords.y + dis;
         }
 
     }
 
     let mut col: Vec<Item> = vec![Item::Unknown; (max_x - min_x) as usize];
     
-    for line in input.split("\n") {
-        let mut sensor_coords = &line[10..];
-        sensor_coords = &sensor_coords[0..sensor_coords.find(":").unwrap()];
-        let beacon_coords = &line[line.rfind("x=").unwrap()..];
-        let sensor_coords = coords_to_idxs(sensor_coords);
-        let beacon_coords = coords_to_idxs(beacon_coords);
-        
+    for &(sensor_coords, beacon_coords) in sensors {
         all_coords_with_mh_dis_add(sensor_coords, beacon_coords,
-            manhattan_distance(sensor_coords, beacon_coords),
-            &mut col, min_x, COLUMN_AT);
+            sensor_coords.manhattan(beacon_coords),
+            &mut col, min_x, column_at);
     }
     
 
@@ -111,94 +122,84 @@ pub fn part1(input: &str) {
         }
     }
 
-    println!("{total}");
+    Ok(total.into())
     
 }
 
-const RANGE: isize = 4000000;
+pub fn part2(sensors: &[Reading]) -> Result<Answer> {
+    part2_with(sensors, &Params::default())
+}
 
-pub fn part2(input: &str) {
-    fn check_others(row: isize, col: isize, cache: &Vec<((isize, isize), usize)>, 
-        sensor: &(isize, isize), dis: &usize) -> bool {
+pub fn part2_with(sensors: &[Reading], params: &Params) -> Result<Answer> {
+    fn check_others(coords: Point2<isize>, cache: &Vec<(Point2<isize>, usize)>, 
+        sensor: &Point2<isize>, dis: &usize) -> bool {
         for (other_sensor, other_dis) in cache {
             if other_sensor == sensor && other_dis == dis {
                 continue;
             }
-            else if manhattan_distance(*other_sensor, (row, col)) <= *other_dis {
+            else if other_sensor.manhattan(coords) <= *other_dis {
                 return false;
             }
         }
         true
     }
-    fn coords_obt(coords: (isize, isize), maxes: (isize, isize), mins: (isize, isize)) -> bool {
-        (coords.0 > maxes.0) || (coords.0 < mins.0) || (coords.1 > maxes.1) || (coords.1 < mins.1)
+    fn coords_obt(coords: Point2<isize>, maxes: Point2<isize>, mins: Point2<isize>) -> bool {
+        (coords.x > maxes.x) || (coords.x < mins.x) || (coords.y > maxes.y) || (coords.y < mins.y)
     }
 
+    let range = params.range;
     let mut cache = Vec::new();
-    for line in input.split("\n") {
-        let mut sensor_coords = &line[10..];
-        sensor_coords = &sensor_coords[0..sensor_coords.find(":").unwrap()];
-        let beacon_coords = &line[line.rfind("x=").unwrap()..];
-        let sensor_coords = coords_to_idxs(sensor_coords);
-        let beacon_coords = coords_to_idxs(beacon_coords);
-        let dis = manhattan_distance(sensor_coords, beacon_coords);
+    for &(sensor_coords, beacon_coords) in sensors {
+        let dis = sensor_coords.manhattan(beacon_coords);
         cache.push((sensor_coords, dis));
     }
     let mut offset = 1;
-    while offset < RANGE {
+    while offset < range {
         for (sensor, dis) in &cache {
             let isize_dis = *dis as isize;
             for x in 0..(isize_dis + offset) {
                 let y = (isize_dis + offset) - x;
-                let mins = (0, 0);
-                let maxes = (RANGE, RANGE);
-
-                let coords = (sensor.0 + x, sensor.1 + y);
-                if !coords_obt(coords, maxes, mins) {
-                    let (row, col) = coords;
-                    if check_others(row, col, &cache, sensor, dis) {
-                        println!("{}", row * 4000000 + col);
-                        return;
-                    }
+                let mins = Point2::new(0, 0);
+                let maxes = Point2::new(range, range);
+
+                let coords = *sensor + Point2::new(x, y);
+                if !coords_obt(coords, maxes, mins) && check_others(coords, &cache, sensor, dis) {
+                    return Ok((coords.x * 4000000 + coords.y).into());
                 }
-                let coords = (sensor.0 - x, sensor.1 + y);
-                if !coords_obt(coords, maxes, mins) {
-                    let (row, col) = coords;
-                    if check_others(row, col, &cache, sensor, dis) {
-                        println!("{}", row * 4000000 + col);
-                        return;
-                    }
+                let coords = *sensor + Point2::new(-x, y);
+                if !coords_obt(coords, maxes, mins) && check_others(coords, &cache, sensor, dis) {
+                    return Ok((coords.x * 4000000 + coords.y).into());
                 }
-                let coords = (sensor.0 + x, sensor.1 - y);
-                if !coords_obt(coords, maxes, mins) {
-                    let (row, col) = coords;
-                    if check_others(row, col, &cache, sensor, dis) {
-                        println!("{}", row * 4000000 + col);
-                        return;
-                    }
+                let coords = *sensor + Point2::new(x, -y);
+                if !coords_obt(coords, maxes, mins) && check_others(coords, &cache, sensor, dis) {
+                    return Ok((coords.x * 4000000 + coords.y).into());
                 }
-                let coords = (sensor.0 - x, sensor.1 - y);
-                if !coords_obt(coords, maxes, mins) {
-                    let (row, col) = coords;
-                    if check_others(row, col, &cache, sensor, dis) {
-                        println!("{}", row * 4000000 + col);
-                        return;
-                    }
+                let coords = *sensor + Point2::new(-x, -y);
+                if !coords_obt(coords, maxes, mins) && check_others(coords, &cache, sensor, dis) {
+                    return Ok((coords.x * 4000000 + coords.y).into());
                 }
             }
         }
         offset += 1;
     }
+    Err(AocError::no_answer(DAY, "no uncovered position found"))
 }
 
-pub fn day15(input: &str) {
-    println!("{}", "Day 15:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
+pub struct Day15;
+
+impl Solution for Day15 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Vec<Reading>>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<Vec<Reading>>()) }
+    fn params(&self) -> Vec<(String, i64)> { Params::defaults() }
+
+    fn solve_with(&self, part: Part, parsed: &Parsed, overrides: &Overrides) -> Result<Answer> {
+        let params = Params::with_overrides(DAY, overrides)?;
+        match part {
+            Part::One => part1_with(parsed.get::<Vec<Reading>>(), &params),
+            Part::Two => part2_with(parsed.get::<Vec<Reading>>(), &params),
+        }
+    }
+}
@@ -1,7 +1,6 @@
-use std::collections::{VecDeque, HashMap};
-use std::time::Instant;
+use crate::{answer::Answer, error::{AocError, Result}, params::{Overrides, ParamSet}, parse::{Line, Lines}, solution::{Parsed, Part, Solution}};
+use std::collections::HashMap;
 use itertools::Itertools;
-use colored::Colorize;
 
 use petgraph::algo::floyd_warshall;
 use petgraph::graph::NodeWeightsMut;
@@ -10,11 +9,13 @@ use petgraph::stable_graph::NodeIndex;
 use petgraph::{Graph, Directed};
 use petgraph::dot::Dot;
 
+const DAY: u32 = 16;
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
-struct Valve<'a> {
+struct Valve {
     pub flow_rate: u32, 
-    pub name: &'a str,
-    pub connections: Vec<&'a str>,
+    pub name: String,
+    pub connections: Vec<String>,
     pub is_on: bool,
 }
 
@@ -38,28 +39,19 @@ impl ValveState {
     }
 }
 
-fn make_valve<'a>(line: &'a str) -> Valve<'a> {
-    let tokens = line.split_ascii_whitespace();
-    let mut tokens = tokens.skip(1);
-    let name = tokens.next().unwrap();
-    let mut tokens = tokens.skip(2);
-    let mut rate = tokens.next().unwrap();
-    rate = &rate[5..];
-    rate = &rate[..rate.len() - 1];
-    let flow_rate = rate.parse().unwrap();
-    let tokens = tokens.skip(4);
-    let mut connections = Vec::new();
-    let mut tokens: VecDeque<&str> = tokens.collect();
-    while tokens.len() > 0 {
-        let item = tokens.pop_front().unwrap();
-        if tokens.len() == 0 {
-            connections.push(item);
-        } else {
-            let len = item.len();
-            connections.push(&item[0..len - 1]);
+/// Reads a `Valve AA has flow rate=0; tunnels lead to valves DD, II, BB` line
+fn make_valve(line: Line) -> Result<Valve> {
+    line.parse(|c| {
+        c.tag("Valve ")?;
+        let name = c.word()?;
+        c.tag(" has flow rate=")?;
+        let flow_rate = c.int()?;
+        if !c.optional("; tunnels lead to valves ") {
+            c.tag("; tunnel leads to valve ")?;
         }
-    }
-    Valve { flow_rate, name, connections, is_on: false}
+        let connections = c.list(", ", |c| c.word().map(String::from))?;
+        Ok(Valve { flow_rate, name: name.to_string(), connections, is_on: false })
+    })
 }
 
 fn score_valves_on_tick(valves: NodeWeightsMut<Valve>) -> u32 {
@@ -135,8 +127,12 @@ struct Movement {
     pub time_left: i32,
 }
 
-const P1_TIME_MAX: u8 = 30;
-const ELY_TIME_MAX: u8 = 26;
+crate::params! {
+    /// Minutes before the volcano erupts when working alone
+    p1_time_max: u8 [1..=30] = 30,
+    /// Minutes left after taking the time to teach the elephant
+    ely_time_max: u8 [1..=30] = 26,
+}
 
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 enum Decision {
@@ -179,22 +175,22 @@ fn is_better(p1: (i32, i32), p2: (i32, i32)) -> bool {
 static HMAP_NO_INSERT_TICK: u8 = 0;
 static INITIAL_HMAP_CAPACITY: usize = 0;
 
-fn branch_with_elephant<'a>(valve_graph: &mut Graph<Valve<'a>, (), Directed>, tick: u8, mut me: Movement, mut ely: Movement,
+fn branch_with_elephant(valve_graph: &mut Graph<Valve, (), Directed>, tick: u8, mut me: Movement, mut ely: Movement,
 states: &mut HashMap<(ValveState, NodeIndex, NodeIndex, u8), (u32, Vec<(u8, Decision, Decision)>)>, 
-distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, Decision)>) 
+distances: &HashMap<(NodeIndex, NodeIndex), i32>, time_max: u8) -> (u32, Vec<(u8, Decision, Decision)>) 
 {
 
     let mut ely_decision = make_decision(valve_graph, tick, &mut ely);
     let mut my_decision = make_decision(valve_graph, tick, &mut me);    
 
-    if tick > ELY_TIME_MAX {
+    if tick > time_max {
         return (0, Vec::new());
     }
     
 
     let base_score = score_valves_on_tick(valve_graph.node_weights_mut());
 
-    if tick == ELY_TIME_MAX {        
+    if tick == time_max {        
         return (base_score, Vec::new())
     }
 
@@ -209,7 +205,7 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, De
             if me.dest.is_some() {
                 me.time_left -= 1;
                 let (score, mut path) = branch_with_elephant(valve_graph, tick + 1, me, ely, 
-                states, distances);
+                states, distances, time_max);
                 path.push((tick, my_decision, ely_decision));
                 return (score + base_score, path)
             }       
@@ -221,7 +217,7 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, De
             let this_valve = valve_graph.node_weight_mut(me.current_loc).unwrap();
             if !this_valve.is_on && this_valve.flow_rate != 0 {
                 this_valve.is_on = true;  
-                (max_score, best_path) = branch_with_elephant(valve_graph, tick + 1, me, ely, states, distances);
+                (max_score, best_path) = branch_with_elephant(valve_graph, tick + 1, me, ely, states, distances, time_max);
                 
                 // Reset state
                 let this_valve = valve_graph.node_weight_mut(me.current_loc).unwrap();
@@ -235,7 +231,7 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, De
                         let v = valve_graph.node_weight(*n).unwrap();
                         !v.is_on &&
                         v.flow_rate != 0 &&
-                        tick + distances[&(me.current_loc, *n)] as u8 <= ELY_TIME_MAX &&
+                        tick + distances[&(me.current_loc, *n)] as u8 <= time_max &&
                         *n != me.current_loc &&
                         *n != ely.dest.unwrap()
                     })
@@ -252,7 +248,7 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, De
         
                     //let decision = format!("Moving on new path: {:?} with distance left {}", new_me.dest.unwrap(), new_me.time_left);            
                     let (score, path) = branch_with_elephant(valve_graph, tick + 1, 
-                        new_me, ely, states, distances);
+                        new_me, ely, states, distances, time_max);
                     if score > max_score {
                         max_score = score;
                         best_path = path;
@@ -262,7 +258,7 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, De
             }
             
             if max_score == 0 {
-                (max_score, best_path) = branch_with_elephant(valve_graph, tick + 1, me, ely, states, distances);
+                (max_score, best_path) = branch_with_elephant(valve_graph, tick + 1, me, ely, states, distances, time_max);
             }
             (max_score, best_path)
         },
@@ -276,7 +272,7 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, De
                 me.time_left -= 1;
                 //let me_dest = me.dest.unwrap();
                 let (score, mut path) = branch_with_elephant(valve_graph, tick + 1, me, ely, 
-                states, distances);
+                states, distances, time_max);
                 path.push((tick, my_decision, ely_decision));
                 // Make sure to reset ely valve
                 let ely_valve = valve_graph.node_weight_mut(ely.current_loc).unwrap();
@@ -293,7 +289,7 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, De
             if !this_valve.is_on && this_valve.flow_rate != 0 {
                 this_valve.is_on = true;  
                 //let decision = String::from("me opening valve ");     
-                (max_score, best_path) = branch_with_elephant(valve_graph, tick + 1, me, ely, states, distances);
+                (max_score, best_path) = branch_with_elephant(valve_graph, tick + 1, me, ely, states, distances, time_max);
                 
                 // Reset state
                 let this_valve = valve_graph.node_weight_mut(me.current_loc).unwrap();
@@ -307,7 +303,7 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, De
                         let v = valve_graph.node_weight(*n).unwrap();
                         !v.is_on &&
                         v.flow_rate != 0 &&
-                        tick + distances[&(me.current_loc, *n)] as u8 <= ELY_TIME_MAX &&
+                        tick + distances[&(me.current_loc, *n)] as u8 <= time_max &&
                         *n != me.current_loc
                     })
                     .map(|n| (n, distances[&(me.current_loc, n)] as u8))
@@ -323,7 +319,7 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, De
         
                     //let decision = format!("Moving on new path: {:?} with distance left {}", new_me.dest.unwrap(), new_me.time_left);            
                     let (score, path) = branch_with_elephant(valve_graph, tick + 1, 
-                        new_me, ely, states, distances);
+                        new_me, ely, states, distances, time_max);
                     if score > max_score {
                         max_score = score;
                         best_path = path;
@@ -334,7 +330,7 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, De
             }
             
             if max_score == 0 {
-                (max_score, best_path) = branch_with_elephant(valve_graph, tick + 1, me, ely, states, distances);
+                (max_score, best_path) = branch_with_elephant(valve_graph, tick + 1, me, ely, states, distances, time_max);
             }
             // Make sure to reset ely valve
             let ely_valve = valve_graph.node_weight_mut(ely.current_loc).unwrap();
@@ -399,7 +395,7 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, De
                         //let p2 = (ely_dis_key_1, me_dis_key_2);
                         let (score, path, me_key, ely_key) = 
                         if is_better(p1, p2) {
-                            if tick + p1.0 as u8 >= ELY_TIME_MAX && tick + p1.1 as u8 >= ELY_TIME_MAX { continue; }
+                            if tick + p1.0 as u8 >= time_max && tick + p1.1 as u8 >= time_max { continue; }
 
                             let me_key = key_list[0];
                             let ely_key = key_list[1];
@@ -416,12 +412,12 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, De
                                 time_left: ely_dis_key_2 - 1,
                             };
                             let (score, path) = branch_with_elephant(valve_graph, tick + 1, 
-                                new_me.clone(), new_ely.clone(), states, distances);
+                                new_me, new_ely, states, distances, time_max);
                             
                             (score, path, me_key, ely_key)
                         }
                         else if is_better(p2, p1) {
-                            if tick + p2.0 as u8 >= ELY_TIME_MAX && tick + p2.1 as u8 >= ELY_TIME_MAX { continue; }
+                            if tick + p2.0 as u8 >= time_max && tick + p2.1 as u8 >= time_max { continue; }
 
                             let me_key = key_list[1];
                             let ely_key = key_list[0];
@@ -438,7 +434,7 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, De
                                 time_left: ely_dis_key_1 - 1,
                             };
                             let (score, path) = branch_with_elephant(valve_graph, tick + 1, 
-                                new_me.clone(), new_ely.clone(), states, distances);
+                                new_me, new_ely, states, distances, time_max);
                             //println!("Distances: {}, {}, {}, {}", me_dis_key_1.0, ely_dis_key_2.0, ely_dis_key_1.0, me_dis_key_2.0);
                             //println!("Chose ely to key 1 and me to key 2");
 
@@ -460,9 +456,9 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, De
                                 time_left: ely_dis_key_2 - 1,
                             };
                             let (score1, path1) = 
-                            if (tick + p1.0 as u8) < ELY_TIME_MAX || (tick + p1.1 as u8) < ELY_TIME_MAX {
+                            if (tick + p1.0 as u8) < time_max || (tick + p1.1 as u8) < time_max {
                                 branch_with_elephant(valve_graph, tick + 1, 
-                                    new_me_1.clone(), new_ely_1.clone(), states, distances)
+                                    new_me_1, new_ely_1, states, distances, time_max)
                             }
                             else {
                                 (0, Vec::new())
@@ -483,9 +479,9 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, De
                                 time_left: ely_dis_key_1 - 1,
                             };
                             let (score2, path2) = 
-                            if (tick + p2.0 as u8) < ELY_TIME_MAX || (tick + p2.1 as u8) < ELY_TIME_MAX {
+                            if (tick + p2.0 as u8) < time_max || (tick + p2.1 as u8) < time_max {
                                 branch_with_elephant(valve_graph, tick + 1, 
-                                    new_me_2.clone(), new_ely_2.clone(), states, distances)
+                                    new_me_2, new_ely_2, states, distances, time_max)
                             }
                             else {
                                 (0, Vec::new())
@@ -511,7 +507,7 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, De
                     }         
                 
                     if max_score == 0 {
-                        (max_score, best_path) = branch_with_elephant(valve_graph, tick + 1, me, ely, states, distances);
+                        (max_score, best_path) = branch_with_elephant(valve_graph, tick + 1, me, ely, states, distances, time_max);
                     }
 
                     // WHY DOES THIS WORK?
@@ -541,7 +537,7 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, De
                         let v = valve_graph.node_weight(*n).unwrap();
                         !v.is_on &&
                         v.flow_rate != 0 &&
-                        tick + distances[&(ely.current_loc, *n)] as u8 <= ELY_TIME_MAX &&
+                        tick + distances[&(ely.current_loc, *n)] as u8 <= time_max &&
                         *n != ely.current_loc &&
                         if me_has_dest { *n != me.dest.unwrap() } else {true}
                     })
@@ -561,7 +557,7 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, De
         
                     //let decision = format!("Moving on new path: {:?} with distance left {}", new_me.dest.unwrap(), new_me.time_left);            
                     let (score, path) = branch_with_elephant(valve_graph, tick + 1, 
-                        me, new_ely, states, distances);
+                        me, new_ely, states, distances, time_max);
                     // TODO REMOVE CLONE
                     if score > max_score {
                         max_score = score;
@@ -580,7 +576,7 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, De
                 }
 
                 if max_score == 0 {
-                    (max_score, best_path) = branch_with_elephant(valve_graph, tick + 1, me, ely, states, distances);
+                    (max_score, best_path) = branch_with_elephant(valve_graph, tick + 1, me, ely, states, distances, time_max);
                 }
                 // Reset state
                 if me.dest.is_none() {
@@ -597,13 +593,9 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>) -> (u32, Vec<(u8, Decision, De
         //best_path.pop();
         //best_path.push((tick, Decision::NothingToDo, Decision::NothingToDo));
         //// Account for the remaining rounds
-        //max_score = (ELY_TIME_MAX - tick) as u32 * base_score;
-        println!("{}", tick);
-        println!("Me: {:?}, decision: {:?}", me, my_decision);
-        println!("Ely: {:?}, decision: {:?}", ely, ely_decision);
-        println!("Best path: {:?}", best_path);
-        println!("{:?}", Dot::with_config(&*valve_graph, &[]));
-        panic!();
+        //max_score = (time_max - tick) as u32 * base_score;
+        panic!("No score at tick {}\nMe: {:?}, decision: {:?}\nEly: {:?}, decision: {:?}\nBest path: {:?}\n{:?}",
+            tick, me, my_decision, ely, ely_decision, best_path, Dot::with_config(&*valve_graph, &[]));
     }
     
     best_path.push((tick, my_decision, ely_decision));
@@ -630,7 +622,7 @@ mut me: Movement, mut ely: Movement) {
     println!("Scoring route");
     let mut score = 0;
     let stopped = false;
-    for tick in 1..=ELY_TIME_MAX {
+    for tick in 1..=Params::default().ely_time_max {
         let tick_score = score_valves_on_tick(valve_graph.node_weights_mut());
         println!("{}: {}", tick, tick_score);
         score += tick_score;
@@ -664,12 +656,20 @@ mut me: Movement, mut ely: Movement) {
     println!("Final score: {}", score)
 }
 
-pub fn part1(input: &str) {
+/// The valves and the tunnels between them, with how far apart every pair of valves is
+pub struct Tunnels {
+    valve_graph: Graph<Valve, (), Directed>,
+    distances: HashMap<(NodeIndex, NodeIndex), i32>,
+    /// Valve AA, where both parts start
+    start: NodeIndex,
+}
+
+pub fn parse(input: &str) -> Result<Tunnels> {
     let mut valve_graph: Graph<Valve, (), Directed> = Graph::new();
     let mut nodes = Vec::new();
 
-    for line in input.split("\n") {
-        let valve = make_valve(line);
+    for line in Lines::new(DAY, input) {
+        let valve = make_valve(line)?;
         let n = valve_graph.add_node(valve);
         nodes.push(n);
     }
@@ -693,10 +693,18 @@ pub fn part1(input: &str) {
     let right_node = nodes.iter().find(|v| {
         let valve = valve_graph.node_weight(**v).unwrap();
         valve.name == "AA"
-    }).unwrap();
+    }).ok_or_else(|| AocError::no_answer(DAY, "there is no valve AA"))?;
+    Ok(Tunnels { valve_graph, distances, start: *right_node })
+}
+
+pub fn part1(tunnels: &Tunnels) -> Result<Answer> {
+    part1_with(tunnels, &Params::default())
+}
 
-    let (max, _path) = branch(&mut valve_graph, 1, *right_node, P1_TIME_MAX, &distances);
-    println!("{}", max);
+pub fn part1_with(tunnels: &Tunnels, params: &Params) -> Result<Answer> {
+    let mut valve_graph = tunnels.valve_graph.clone();
+    let (max, _path) = branch(&mut valve_graph, 1, tunnels.start, params.p1_time_max, &tunnels.distances);
+    Ok(max.into())
 }
 
 fn _test_pt2(valve_graph: &mut Graph<Valve, (), Directed>, 
@@ -708,7 +716,7 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>)
     // ELY HAS JUST REACHED LA
     let turn_on = ["AA", "FA", "GA", "HA"];
     for valve in valve_graph.node_weights_mut() {
-        if turn_on.contains(&valve.name) {
+        if turn_on.contains(&valve.name.as_str()) {
             valve.is_on = true;
         }
     }
@@ -738,7 +746,8 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>)
         dest: None,
         time_left: 0,
     };
-    let (_, path) = branch_with_elephant(valve_graph, 11, me, ely, states, distances);
+    let time_max = Params::default().ely_time_max;
+    let (_, path) = branch_with_elephant(valve_graph, 11, me, ely, states, distances, time_max);
     for (tick, me, ely) in path.iter().rev() {
         println!("{}: {:?}, {:?}", tick, me, ely);
     }
@@ -746,58 +755,40 @@ distances: &HashMap<(NodeIndex, NodeIndex), i32>)
 
 }
 
-pub fn part2(input: &str) {
-    let mut valve_graph: Graph<Valve, (), Directed> = Graph::new();
-    let mut states = HashMap::with_capacity(INITIAL_HMAP_CAPACITY);
-    let mut nodes = Vec::new();
-
-    for line in input.split("\n") {
-        let valve = make_valve(line);
-        let n = valve_graph.add_node(valve);
-        nodes.push(n);
-    }
-    // Make connections
-    for node in &nodes {
-        for other_node in &nodes {
-            if node == other_node { continue; }
-            let valve = valve_graph.node_weight(*node).unwrap();
-            let other_valve = valve_graph.node_weight(*other_node).unwrap();
-            if valve.connections.contains(&other_valve.name) {
-                valve_graph.extend_with_edges(&[(*node, *other_node)]);
-            }
-        }
-    }
-    for valve in valve_graph.node_weights_mut() {
-        valve.connections.clear();
-    }
-
-    let distances = floyd_warshall(&valve_graph, |_| 1).unwrap();
+pub fn part2(tunnels: &Tunnels) -> Result<Answer> {
+    part2_with(tunnels, &Params::default())
+}
 
-    //println!("{:?}", Dot::with_config(&valve_graph, &[]));
-    let right_node = nodes.iter().find(|v| {
-        let valve = valve_graph.node_weight(**v).unwrap();
-        valve.name == "AA"
-    }).unwrap();
+pub fn part2_with(tunnels: &Tunnels, params: &Params) -> Result<Answer> {
+    let mut valve_graph = tunnels.valve_graph.clone();
+    let mut states = HashMap::with_capacity(INITIAL_HMAP_CAPACITY);
 
     let me = Movement { 
-        current_loc: *right_node,
+        current_loc: tunnels.start,
         dest: None,
         time_left: 0,
     };
 
     let ely = me.clone();
-    let (max, _path) = branch_with_elephant(&mut valve_graph, 1, me, ely, &mut states, &distances);
-    println!("{}", max);
+    let (max, _path) = branch_with_elephant(&mut valve_graph, 1, me, ely, &mut states, &tunnels.distances, params.ely_time_max);
+    Ok(max.into())
 }
 
-pub fn day16(input: &str) {
-    println!("{}", "Day 16:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
+pub struct Day16;
+
+impl Solution for Day16 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Tunnels>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<Tunnels>()) }
+    fn params(&self) -> Vec<(String, i64)> { Params::defaults() }
+
+    fn solve_with(&self, part: Part, parsed: &Parsed, overrides: &Overrides) -> Result<Answer> {
+        let params = Params::with_overrides(DAY, overrides)?;
+        match part {
+            Part::One => part1_with(parsed.get::<Tunnels>(), &params),
+            Part::Two => part2_with(parsed.get::<Tunnels>(), &params),
+        }
+    }
+}
@@ -1,320 +1,146 @@
-use std::{collections::VecDeque, vec, time::Instant};
-use colored::Colorize;
+use crate::{answer::Answer, error::Result, params::{Overrides, ParamSet}, parse::Lines, solution::{Parsed, Part, Solution}, utils::grid::Grid};
+use std::collections::VecDeque;
+
+const DAY: u32 = 17;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
-enum Movement {
+pub enum Movement {
     Left,
     Right
 }
 
-#[derive(Clone, Copy)]
-enum Rock {
-    Flat,
-    Cross,
-    L,
-    Line,
-    Square,
-}
-
-fn parse_movements(input: &str) -> VecDeque<Movement> {
+/// The cells each rock covers, as (column, row) from its bottom left corner, in the order
+/// they fall
+const ROCKS: [&[(usize, usize)]; 5] = [
+    // Flat
+    &[(0, 0), (1, 0), (2, 0), (3, 0)],
+    // Cross
+    &[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
+    // L
+    &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+    // Line
+    &[(0, 0), (0, 1), (0, 2), (0, 3)],
+    // Square
+    &[(0, 0), (1, 0), (0, 1), (1, 1)],
+];
+
+const CHAMBER_WIDTH: usize = 7;
+
+/// Reads the single line of `<` and `>` jets
+pub fn parse(input: &str) -> Result<VecDeque<Movement>> {
+    let mut lines = Lines::new(DAY, input);
+    let line = lines.expect("a line of jets")?;
+    if let Some(extra) = lines.next() {
+        return Err(extra.error_at(1, "end of input"));
+    }
     let mut movements= VecDeque::new();
-    for b in input.as_bytes() {
-        movements.push_back(match *b as char {
+    for (column, c) in line.columns() {
+        movements.push_back(match c {
             '<' => Movement::Left,
             '>' => Movement::Right,
-            _ => unreachable!(),
+            _ => return Err(line.error_at(column, "'<' or '>'")),
         });
     }
-    movements
+    if movements.is_empty() {
+        return Err(line.error_at(1, "a jet"));
+    }
+    Ok(movements)
 }
 
-fn sim_movements(lines: &mut Vec<Vec<&str>>, movement: Movement) -> bool {
-    // First, check if we can make the move 
-    let mut can_make_movement = true;
-    if movement == Movement::Left {
-        for line_idx in 0..lines.len() {
-            let line = &lines[line_idx];
-            for shape_idx in 0..line.len() {
-                let line = &lines[line_idx];
-                if line[shape_idx] != "@" { continue; }
-                if line_idx == 0 || lines[line_idx - 1][shape_idx] == "#" {
-                    can_make_movement = false;
-                }
-            }
-        } 
+/// The settled rocks, with the floor at row 0, and the rock still falling
+struct Chamber {
+    rows: Grid<char>,
+    rock: &'static [(usize, usize)],
+    /// The falling rock's bottom left corner, as (column, row)
+    at: (usize, usize),
+}
+
+impl Chamber {
+    fn new(rock: &'static [(usize, usize)]) -> Chamber {
+        Chamber { rows: Grid::new(0, CHAMBER_WIDTH, '.'), rock, at: (2, 3) }
     }
-    else {
-        for line_idx in (0..lines.len()).rev() {
-            let line = &lines[line_idx];
-            for shape_idx in 0..line.len() {
-                let line = &lines[line_idx];
-                if line[shape_idx] != "@" { continue; }
 
-                if line_idx == lines.len() - 1 || lines[line_idx + 1][shape_idx] == "#" {
-                    can_make_movement = false;
-                }
-            }
-            
-        }
+    /// Whether the falling rock would fit with its corner at `at`
+    fn fits(&self, at: (usize, usize)) -> bool {
+        self.rock.iter().all(|&(col, row)| {
+            let (col, row) = (at.0 + col, at.1 + row);
+            col < CHAMBER_WIDTH && self.rows.get((row, col)).is_none_or(|&cell| cell == '.')
+        })
     }
-    
-    // Then, sim movements
-    if can_make_movement {
-        if movement == Movement::Left {
-            for line_idx in 0..lines.len() {
-                let line = &lines[line_idx];
-                for shape_idx in 0..line.len() {
-                    let line = &lines[line_idx];
-                    if line[shape_idx] != "@" { continue; }
-    
-                    if line_idx == 0 {
-                        continue;
-                    }
-                    if lines[line_idx - 1][shape_idx] != "." {
-                        continue;
-                    }
-                    lines[line_idx - 1][shape_idx] = "@";
-                    lines[line_idx][shape_idx] = ".";
-                }
-            } 
-        }
-        else {
-            for line_idx in (0..lines.len()).rev() {
-                let line = &lines[line_idx];
-                for shape_idx in 0..line.len() {
-                    let line = &lines[line_idx];
-                    if line[shape_idx] != "@" { continue; }
 
-                    if line_idx == lines.len() - 1 {
-                        continue;
-                    }
-                    if lines[line_idx + 1][shape_idx] != "." {
-                        continue;
-                    }
-                    lines[line_idx + 1][shape_idx] = "@";
-                    lines[line_idx][shape_idx] = ".";
-                }
-                
-            }
+    /// Pushes the falling rock with a jet then lets it fall a row, saying whether it came to rest
+    fn sim_movement(&mut self, movement: Movement) -> bool {
+        let pushed = match movement {
+            Movement::Left => self.at.0.checked_sub(1).map(|col| (col, self.at.1)),
+            Movement::Right => Some((self.at.0 + 1, self.at.1)),
+        };
+        if let Some(pushed) = pushed.filter(|&pushed| self.fits(pushed)) {
+            self.at = pushed;
         }
-    }
 
-    // Then, check if we can move down
-    let mut can_move_down = true;
-    for line in &mut *lines {
-        let shape_idx = line.iter().position(|&s| s == "@");
-        if shape_idx.is_none() { continue; }
-        let shape_idx = shape_idx.unwrap();
-        if shape_idx == 0 || line[shape_idx - 1] != "." {
-            can_move_down = false;
+        if let Some(fallen) = self.at.1.checked_sub(1).map(|row| (self.at.0, row)).filter(|&fallen| self.fits(fallen)) {
+            self.at = fallen;
+            return false;
         }
-    }
-    if !can_move_down {
-        for line in lines {
-            for shape_idx in 0..line.len() {
-                if line[shape_idx] != "@" { continue; }
-                line[shape_idx] = "#";
+        for &(col, row) in self.rock {
+            let (col, row) = (self.at.0 + col, self.at.1 + row);
+            while self.rows.height() <= row {
+                self.rows.push_row(['.'; CHAMBER_WIDTH]);
             }
+            self.rows[(row, col)] = '#';
         }
         true
     }
-    else {
-        for line in lines {
-            for shape_idx in 0..line.len() {
-                if line[shape_idx] != "@" { continue; }
-                line[shape_idx] = ".";
-                line[shape_idx - 1] = "@";
-            }
-        }
-        false
-    }
-    
-}
 
-fn spawn_rock(lines: &mut Vec<Vec<&str>>, rock: Rock) {
-    // Add three new layers
-    for line in &mut *lines {
-        line.push(".");
-        line.push(".");
-        line.push(".");
+    /// Starts `rock` falling three rows above the top of the pile
+    fn spawn_rock(&mut self, rock: &'static [(usize, usize)]) {
+        self.rock = rock;
+        self.at = (2, self.rows.height() + 3);
     }
-    match rock {
-        Rock::Flat => {
-            for line_idx in 0..7 {
-                let line = &mut lines[line_idx];
-                if line_idx > 1 && line_idx < 6 {
-                    line.push("@");
-                }
-                else {
-                    line.push(".");
-                }
-            }
-        },
-        Rock::Cross => {
-            for line_idx in 0..7 {
-                let line = &mut lines[line_idx];
-                if line_idx == 2 || line_idx == 4 {
-                    line.push(".");
-                    line.push("@");
-                    line.push(".");
-
-                }
-                else if line_idx == 3 {
-                    line.push("@");
-                    line.push("@");
-                    line.push("@");
-                }
-                else {       
-                    line.push(".");
-                    line.push(".");
-                    line.push(".");
-                }
-            }
-        },
-        Rock::L => {
-            for line_idx in 0..7 {
-                let line = &mut lines[line_idx];
-                if line_idx == 2 || line_idx == 3 {
-                    line.push("@");
-                    line.push(".");
-                    line.push(".");
-
-                }
-                else if line_idx == 4 {
-                    line.push("@");
-                    line.push("@");
-                    line.push("@");
-                }
-                else {       
-                    line.push(".");
-                    line.push(".");
-                    line.push(".");
-                }
-            }
-        },
-        Rock::Line => {
-            for line_idx in 0..7 {
-                let line = &mut lines[line_idx];
-                if line_idx == 2 {
-                    line.push("@");
-                    line.push("@");
-                    line.push("@");
-                    line.push("@");
-                }
-                else {       
-                    line.push(".");
-                    line.push(".");
-                    line.push(".");
-                    line.push(".");
-                }
-            }
-
-        },
-        Rock::Square => {
-            for line_idx in 0..7 {
-                let line = &mut lines[line_idx];
-                if line_idx == 2 || line_idx == 3 {
-                    line.push("@");
-                    line.push("@");
 
-                }
-                else {       
-                    line.push(".");
-                    line.push(".");
-                }
-            }
-        },
+    fn height(&self) -> usize {
+        self.rows.height()
     }
-}
 
-fn _print_lines(lines: &mut Vec<Vec<&str>>) {
-    for idx in (0..lines[0].len()).rev() {
-        for line in &*lines {
-            print!("{}", line[idx]);
-        }
-        println!();
+    /// Forgets the rows under the highest full one, since nothing can fall past it, giving
+    /// how many were dropped
+    fn clear_lines(&mut self) -> usize {
+        let Some(full) = (0..self.rows.height()).rev().find(|&row| self.rows.row(row).iter().all(|&cell| cell == '#')) else {
+            return 0;
+        };
+        self.rows.remove_rows(0..full);
+        self.at.1 -= full;
+        full
     }
 }
 
-fn _print_lines_with_limit(lines: &mut Vec<Vec<&str>>, limit: usize) {
-    let cap = lines[0].len() - 1;
-    for idx in 0..limit.min(cap + 1) {
-        for line in &*lines {
-            print!("{}", line[cap - idx]);
-        }
-        println!();
-    }
+crate::params! {
+    /// How many rocks fall in part 1
+    p1_num_lines: i128 = 2022,
+    /// How many rocks fall in part 2
+    p2_num_lines: i128 = 1000000000000,
 }
 
-fn clear_lines(lines: &mut Vec<Vec<&str>>) -> Option<usize> {
-    // try to find an empty line
-    let mut empty_idx = None;
-    for idx in (0..lines[0].len()).rev() { 
-        let mut line_clear = true;
-        for line in &*lines {
-            if line[idx] != "#" { line_clear = false }
-        }
-        if line_clear {
-            empty_idx = Some(idx);
-            break;
-        }
-    }
-    if let Some(empty_idx) = empty_idx {
-        for line_idx in 0..lines.len() {
-            let new_line = lines[line_idx].split_off(empty_idx); 
-            lines[line_idx] = new_line;
-        }
-    }
-
-    empty_idx
-
+pub fn part1(movements: &VecDeque<Movement>) -> Result<Answer> {
+    part1_with(movements, &Params::default())
 }
 
-const P1_NUM_LINES: i128 = 2022;
-
-pub fn part1(input: &str) {
-    let mut movements = parse_movements(input);
-    let mut lines = vec![vec![], vec![], vec![], vec![], vec![], vec![], vec![]];
-    let mut rocks = VecDeque::from([Rock::Flat, Rock::Cross, Rock::L, Rock::Line, Rock::Square]);
+pub fn part1_with(movements: &VecDeque<Movement>, params: &Params) -> Result<Answer> {
+    let mut movements = movements.iter().cycle();
+    let mut rocks = ROCKS.iter().cycle();
     let mut fallen: i128 = 0;
     // Spawn first rock
-    let rock = rocks.pop_front().unwrap();
-    spawn_rock(&mut lines, rock);
-    rocks.push_back(rock);
-    while fallen < P1_NUM_LINES {
-
-        let movement = movements.pop_front().unwrap();
-        let res = sim_movements(&mut lines, movement);
-        movements.push_back(movement);        
-        // Skim top
-        let mut top_empty = true;
-        for line in &lines {
-            if line.last().is_none() || *line.last().unwrap() != "." {
-                top_empty = false;
-            } 
-        }
-        if top_empty {
-            for line in &mut lines {
-                line.pop();
-            }
-        }
-
-        if res {
+    let mut chamber = Chamber::new(rocks.next().unwrap());
+    while fallen < params.p1_num_lines {
+        let movement = *movements.next().unwrap();
+        if chamber.sim_movement(movement) {
             fallen += 1;
-            // Don't create new rock if end
-            if fallen >= P1_NUM_LINES {
-                break;
-            }
-            let rock = rocks.pop_front().unwrap();
-            spawn_rock(&mut lines, rock);
-            rocks.push_back(rock);
+            chamber.spawn_rock(rocks.next().unwrap());
         }
     }
-    println!("{}", lines.iter().map(|v| v.len()).max().unwrap());
+    Ok(chamber.height().into())
 }
 
-const P2_NUM_LINES: i128 = 1000000000000;
-
 fn detect_cycle(cache: &Vec<(i128, usize, usize, u128)>) -> Option<(u128, usize)> {
     let start = cache.last()?;
     let mut first = None;
@@ -349,55 +175,35 @@ fn detect_cycle(cache: &Vec<(i128, usize, usize, u128)>) -> Option<(u128, usize)
 
 } 
 
-pub fn part2(input: &str) {
-    let movements = parse_movements(input);
+pub fn part2(movements: &VecDeque<Movement>) -> Result<Answer> {
+    part2_with(movements, &Params::default())
+}
+
+pub fn part2_with(movements: &VecDeque<Movement>, params: &Params) -> Result<Answer> {
     let mut movement_idx = 0;
-    let mut lines = vec![vec![], vec![], vec![], vec![], vec![], vec![], vec![]];
-    let rocks = VecDeque::from([Rock::Flat, Rock::Cross, Rock::L, Rock::Line, Rock::Square]);
     let mut rock_idx = 0;
     let mut fallen: i128 = 0;
-    //let mut map = HashMap::new();
-
 
     // Spawn first rock
-    let rock = rocks[rock_idx];
-    spawn_rock(&mut lines, rock);
+    let mut chamber = Chamber::new(ROCKS[rock_idx]);
     rock_idx += 1;
-    rock_idx %= rocks.len();
+    rock_idx %= ROCKS.len();
     let mut cleared_lines: u128 = 0;
     let mut cache = Vec::new();
 
-
-    while fallen < P2_NUM_LINES {
+    while fallen < params.p2_num_lines {
         let movement = movements[movement_idx];
-        let res = sim_movements(&mut lines, movement);
+        let res = chamber.sim_movement(movement);
         movement_idx += 1;
         movement_idx %= movements.len();
-        // Skim top
-        let mut top_empty = true;
-        for line in &lines {
-            if line.last().is_none() || *line.last().unwrap() != "." {
-                top_empty = false;
-            } 
-        }
-        if top_empty {
-            for line in &mut lines {
-                line.pop();
-            }
-        }
-
-        let line_num = clear_lines(&mut lines);
-        if let Some(line_num) = line_num {
-            cleared_lines += line_num as u128;
-        }
 
         if res {
+            cleared_lines += chamber.clear_lines() as u128;
             fallen += 1;
             if rock_idx == 0 {
-                cache.push((fallen, rock_idx, movement_idx, 
-                    lines.iter().map(|v| v.len()).max().unwrap() as u128 + cleared_lines));
+                cache.push((fallen, rock_idx, movement_idx, chamber.height() as u128 + cleared_lines));
                 if let Some((fallen_in_cycle, rock_diff)) = detect_cycle(&cache) {
-                    let num_left = (P2_NUM_LINES - fallen) as u128;
+                    let num_left = (params.p2_num_lines - fallen) as u128;
                     let num_cycles = num_left / rock_diff as u128;
                     cleared_lines += num_cycles * fallen_in_cycle;
                     fallen += (num_cycles * rock_diff as u128) as i128;
@@ -405,30 +211,29 @@ pub fn part2(input: &str) {
                 }
             }
 
-            // Don't create new rock if end
-            if fallen >= P2_NUM_LINES {
-                break;
-            }
-
-            let rock = rocks[rock_idx];
-            spawn_rock(&mut lines, rock);
+            chamber.spawn_rock(ROCKS[rock_idx]);
             rock_idx += 1;
-            rock_idx %= rocks.len();
+            rock_idx %= ROCKS.len();
         }
-
-        
     }
-    println!("{}", lines.iter().map(|v| v.len()).max().unwrap() as u128 + cleared_lines);
+    Ok((chamber.height() as u128 + cleared_lines).into())
 }
 
-pub fn day17(input: &str) {
-    println!("{}", "Day 17:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
+pub struct Day17;
+
+impl Solution for Day17 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<VecDeque<Movement>>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<VecDeque<Movement>>()) }
+    fn params(&self) -> Vec<(String, i64)> { Params::defaults() }
+
+    fn solve_with(&self, part: Part, parsed: &Parsed, overrides: &Overrides) -> Result<Answer> {
+        let params = Params::with_overrides(DAY, overrides)?;
+        match part {
+            Part::One => part1_with(parsed.get::<VecDeque<Movement>>(), &params),
+            Part::Two => part2_with(parsed.get::<VecDeque<Movement>>(), &params),
+        }
+    }
+}
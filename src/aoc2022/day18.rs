@@ -1,182 +1,54 @@
-use std::collections::HashMap;
-use std::time::Instant;
-use colored::Colorize;
-
-use petgraph::Graph;
-use petgraph::algo::astar;
-
-fn get_coords(input: &str) -> Vec<(usize, usize, usize)> {
-    let mut coords = Vec::new();
-    for line in input.split("\n") {
-        let mut coord_iter = line.split(",");
-        let x = coord_iter.next().unwrap().parse().unwrap();        
-        let y = coord_iter.next().unwrap().parse().unwrap();    
-        let z = coord_iter.next().unwrap().parse().unwrap();     
-        coords.push((x, y, z));   
-    }
-    coords
+use crate::{answer::Answer, error::Result, parse::Lines, solution::{Parsed, Solution}, utils::{grid3::Pos3, Grid3}};
+
+const DAY: u32 = 18;
+
+/// Reads each `x,y,z` cube
+fn get_coords(input: &str) -> Result<Vec<Pos3>> {
+    Lines::new(DAY, input).map(|line| line.parse(|c| {
+        let x = c.int()?;
+        c.tag(",")?;
+        let y = c.int()?;
+        c.tag(",")?;
+        let z = c.int()?;
+        Ok((x, y, z))
+    })).collect()
 }
 
-fn get_max_dimensions(coords: &Vec<(usize, usize, usize)>) ->  (isize, isize, isize) {
-    let mut max_x = 0;
-    let mut max_y = 0;
-    let mut max_z = 0;
-    for coord in coords {
-        if coord.0 > max_x {
-            max_x = coord.0;
-        }
-        if coord.1 > max_y {
-            max_y = coord.1;
-        }
-        if coord.2 > max_z {
-            max_z = coord.2
-        }
+/// Which positions the droplet's cubes fill, with a layer of air all the way round
+pub fn parse(input: &str) -> Result<Grid3<bool>> {
+    let coords = get_coords(input)?;
+    let min = coords.iter().fold((0, 0, 0), |min, &(x, y, z)| (min.0.min(x), min.1.min(y), min.2.min(z)));
+    let max = coords.iter().fold((0, 0, 0), |max, &(x, y, z)| (max.0.max(x), max.1.max(y), max.2.max(z)));
+    let mut cubes = Grid3::new((min.0 - 1, min.1 - 1, min.2 - 1), (max.0 + 1, max.1 + 1, max.2 + 1), false);
+    for pos in coords {
+        cubes[pos] = true;
     }
-    (max_x as isize + 1, max_y as isize + 1, max_z as isize + 1)
+    Ok(cubes)
 }
 
-fn surface_area(cubes: &Vec<Vec<Vec<bool>>>, maxes: (isize, isize, isize)) -> usize {
-    let (max_x, max_y, max_z) = maxes;
-    let mut uncovered_sides = 0;
-    for x in 0..max_x {
-        for y in 0..max_y {
-            for z in 0..max_z {
-                if !cubes[x as usize][y as usize][z as usize] { continue; }
-
-                for x_offset in  [-1, 1] {
-                    let actual_x = x + x_offset;
-                    if actual_x < 0 || actual_x >= max_x || cubes[actual_x as usize][y as usize][z as usize] == false {
-                        uncovered_sides += 1;
-                    }
-                }
-                for y_offset in  [-1, 1] {
-                    let actual_y = y + y_offset;
-                    if actual_y < 0 || actual_y >= max_y || cubes[x as usize][actual_y as usize][z as usize] == false {
-                        uncovered_sides += 1;
-                    }
-                }
-                for z_offset in  [-1, 1] {
-                    let actual_z = z + z_offset;
-                    if actual_z < 0 || actual_z >= max_z || cubes[x as usize][y as usize][actual_z as usize] == false {
-                        uncovered_sides += 1;
-                    }
-                }
-            }
-        }
-    }   
-    uncovered_sides
+/// How many faces of the cubes touch a cell `open` says is open to them
+fn surface_area(cubes: &Grid3<bool>, open: impl Fn(Pos3) -> bool) -> usize {
+    cubes.iter()
+        .filter(|(_, cube)| **cube)
+        .map(|(pos, _)| cubes.neighbors6(pos).filter(|&other| open(other)).count())
+        .sum()
 }
 
-pub fn part1(input: &str) {
-    let coords = get_coords(input);
-    let (max_x, max_y, max_z) = get_max_dimensions(&coords);
-    let mut cubes = vec![vec![vec![false; max_z as usize]; max_y as usize]; max_x as usize];
-    for (x, y, z) in coords {
-        cubes[x][y][z] = true;
-    }
-    //println!("{:?}", cubes);
-    let uncovered_sides = surface_area(&cubes, (max_x, max_y, max_z));
-
-    println!("{}", uncovered_sides)
+pub fn part1(cubes: &Grid3<bool>) -> Result<Answer> {
+    Ok(surface_area(cubes, |pos| !cubes[pos]).into())
 }
 
-pub fn part2(input: &str) {
-    let coords = get_coords(input);
-    let (max_x, max_y, max_z) = get_max_dimensions(&coords);
-    let mut cubes = vec![vec![vec![false; max_z as usize]; max_y as usize]; max_x as usize];
-    for (x, y, z) in coords {
-        cubes[x][y][z] = true;
-    }
-    // Create graph of all air nodes
-    let mut air_graph = Graph::new();
-    let external_node = air_graph.add_node(());
-    let mut coords_to_node_map = HashMap::new();
-    for x in 0..max_x {
-        for y in 0..max_y {
-            for z in 0..max_z {
-                let xu = x as usize;
-                let yu = y as usize;
-                let zu = z as usize;
-                if cubes[xu][yu][zu] { continue; }
-                let node  = air_graph.add_node(());
-                // If node on outside, connect to extenal
-                if x == 0 || x == max_x - 1 
-                || y == 0 || y == max_y - 1 
-                || z == 0 || z == max_z - 1 {
-                    air_graph.add_edge(node, external_node, ());
-                } 
-                coords_to_node_map.insert((x, y, z), node);
-            }
-        }
-    }
-
-    // connect adjactent air nodes
-    for x in 0..max_x {
-        for y in 0..max_y {
-            for z in 0..max_z {
-                if let Some(node) = coords_to_node_map.get(&(x, y, z)) {
-                    let node = *node;
-                    for x_offset in  [-1, 1] {
-                        let actual_x = x + x_offset;
-                        if let Some(other_node) = coords_to_node_map.get(&(actual_x, y, z)) {
-                            let other_node = *other_node;
-                            air_graph.add_edge(node, other_node, ());
-                        }
-                    }
-                    for y_offset in  [-1, 1] {
-                        let actual_y = y + y_offset;
-                        if let Some(other_node) = coords_to_node_map.get(&(x, actual_y, z)) {
-                            let other_node = *other_node;
-                            air_graph.add_edge(node, other_node, ());
-                        }
-                    }
-                    for z_offset in  [-1, 1] {
-                        let actual_z = z + z_offset;
-                        if let Some(other_node) = coords_to_node_map.get(&(x, y, actual_z)) {
-                            let other_node = *other_node;
-                            air_graph.add_edge(node, other_node, ());
-                        }
-                    }
-                }
-            }
-        }
-    }
-    // Actually see if nodes are accesible
-    let mut coords_to_mutate = Vec::new();
-    for x in 0..max_x {
-        for y in 0..max_y {
-            for z in 0..max_z {
-                let xu = x as usize;
-                let yu = y as usize;
-                let zu = z as usize;
-                if cubes[xu][yu][zu] { continue; }
-                let node = *coords_to_node_map.get(&(x, y, z)).unwrap();
-                let path = astar(&air_graph, node, 
-                |f| f == external_node, |_| 0, |_| 0);
-                if path.is_none() {
-                    coords_to_mutate.push((xu, yu, zu));
-                }
-            }
-        }
-    }
-    
-    for (x, y, z) in coords_to_mutate {
-        cubes[x][y][z] = true;
-    }
+pub fn part2(cubes: &Grid3<bool>) -> Result<Answer> {
+    let outside = cubes.flood_fill_exterior(|&cube| !cube);
+    Ok(surface_area(cubes, |pos| outside[pos]).into())
+}
 
-    //println!("{:?}", cubes);
-    let uncovered_sides = surface_area(&cubes, (max_x, max_y, max_z));
+pub struct Day18;
 
-    println!("{}", uncovered_sides) 
+impl Solution for Day18 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Grid3<bool>>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<Grid3<bool>>()) }
 }
-pub fn day18(input: &str) {
-    println!("{}", "Day 18:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
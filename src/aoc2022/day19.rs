@@ -1,7 +1,10 @@
-use std::{ops::{Sub, Index}, cmp::Ordering, time::Instant};
-use colored::Colorize;
+use crate::{answer::Answer, error::Result, params::{Overrides, ParamSet}, parse::Lines, solution::{Parsed, Part, Solution}};
+use std::{ops::{Sub, Index}, cmp::Ordering};
 
 use itertools::Itertools;
+
+const DAY: u32 = 19;
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
 struct Cost {
     pub num_ore: u8,    
@@ -66,7 +69,7 @@ impl Cost {
 }
 
 #[derive(Clone, PartialEq, Eq, Debug, Hash, Copy)]
-struct Blueprint {
+pub struct Blueprint {
     ore_robot: Cost,
     clay_robot: Cost,
     obsidian_robot: Cost,        
@@ -106,8 +109,12 @@ struct Simulation {
     pub stage: Stage,
 }
 
-const P1_TIME_LIMIT: u8 = 24;
-const P2_TIME_LIMIT: u8 = 32;
+crate::params! {
+    /// Minutes each blueprint runs for in part 1
+    p1_time_limit: u8 [1..=32] = 24,
+    /// Minutes each of the first three blueprints runs for in part 2
+    p2_time_limit: u8 [1..=32] = 32,
+}
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
 enum Stage {
@@ -315,88 +322,93 @@ impl PartialOrd for Simulation {
 
 impl Ord for Simulation {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        assert!(self.time >= P1_TIME_LIMIT);
-        assert!(other.time >= P1_TIME_LIMIT);
+        // Geodes are only worth comparing once both simulations have run out of time
+        assert_eq!(self.time, other.time);
         self.num_geodes.cmp(&other.num_geodes)
     }
 }
 
-fn parse_blueprints(input: &str) -> Vec<Blueprint> {
-    let mut prints = Vec::new();
-    for line in input.split("\n") {
-        let it = line.split_ascii_whitespace();
-        let mut it = it.skip(1);
-        let num = it.next().unwrap();
-        let num = num[0..num.len() - 1].parse().unwrap();
-        let mut it = it.skip(4);
-        let ore_cost = it.next().unwrap().parse().unwrap();
-        let ore_robot = Cost { num_ore: ore_cost, num_clay: 0, num_obsidian: 0 };
-
-        let mut it = it.skip(5);
-        let clay_cost = it.next().unwrap().parse().unwrap();
-        let clay_robot = Cost { num_ore: clay_cost, num_clay: 0, num_obsidian: 0 };
-
-        let mut it = it.skip(5);
-        let ob_cost_p1 = it.next().unwrap().parse().unwrap();
-        let mut it = it.skip(2);
-        let ob_cost_p2 = it.next().unwrap().parse().unwrap();
+/// Reads one `Blueprint N: Each ore robot costs ...` line per blueprint
+pub fn parse(input: &str) -> Result<Vec<Blueprint>> {
+    Lines::new(DAY, input).map(|line| line.parse(|c| {
+        c.tag("Blueprint ")?;
+        let number = c.int()?;
+        c.tag(": Each ore robot costs ")?;
+        let ore_robot = Cost { num_ore: c.int()?, num_clay: 0, num_obsidian: 0 };
+        c.tag(" ore. Each clay robot costs ")?;
+        let clay_robot = Cost { num_ore: c.int()?, num_clay: 0, num_obsidian: 0 };
+        c.tag(" ore. Each obsidian robot costs ")?;
+        let ob_cost_p1 = c.int()?;
+        c.tag(" ore and ")?;
+        let ob_cost_p2 = c.int()?;
         let obsidian_robot = Cost { num_ore: ob_cost_p1, num_clay: ob_cost_p2, num_obsidian: 0 };
-
-        let mut it = it.skip(5);
-        let gd_cost_p1 = it.next().unwrap().parse().unwrap();
-        let mut it = it.skip(2);
-        let gd_cost_p2 = it.next().unwrap().parse().unwrap();
+        c.tag(" clay. Each geode robot costs ")?;
+        let gd_cost_p1 = c.int()?;
+        c.tag(" ore and ")?;
+        let gd_cost_p2 = c.int()?;
         let geode_robot = Cost { num_ore: gd_cost_p1, num_clay: 0, num_obsidian: gd_cost_p2 };
+        c.tag(" obsidian.")?;
 
-        prints.push(Blueprint { 
+        Ok(Blueprint {
             ore_robot,
-            clay_robot, 
-            obsidian_robot, 
-            geode_robot, 
-            number: num,
-        });
-    }
-    prints
+            clay_robot,
+            obsidian_robot,
+            geode_robot,
+            number,
+        })
+    })).collect()
+}
+
+pub fn part1(prints: &[Blueprint]) -> Result<Answer> {
+    part1_with(prints, &Params::default())
 }
 
-pub fn part1(input: &str) {
-    let prints = parse_blueprints(input);
+pub fn part1_with(prints: &[Blueprint], params: &Params) -> Result<Answer> {
     let mut sims = Vec::new();
-    for print in prints {
+    for &print in prints {
         sims.push(Simulation::new(print));
-        sims.last_mut().unwrap().find_best_limiting_reactant(P1_TIME_LIMIT);
+        sims.last_mut().unwrap().find_best_limiting_reactant(params.p1_time_limit);
     }
     let mut levels = 0;
     for sim in sims {
         levels += sim.num_geodes * sim.blueprint.number;
     }
-    println!("{}", levels);
+    Ok(levels.into())
     
 }
 
-pub fn part2(input: &str) {
-    let mut prints = parse_blueprints(input);
-    prints.truncate(3);
+pub fn part2(prints: &[Blueprint]) -> Result<Answer> {
+    part2_with(prints, &Params::default())
+}
+
+pub fn part2_with(prints: &[Blueprint], params: &Params) -> Result<Answer> {
     let mut sims = Vec::new();
-    for print in prints {
+    for &print in prints.iter().take(3) {
         sims.push(Simulation::new(print));
-        sims.last_mut().unwrap().find_best_limiting_reactant(P2_TIME_LIMIT);
+        sims.last_mut().unwrap().find_best_limiting_reactant(params.p2_time_limit);
     }
     let mut levels = 1;
     for sim in sims {
         levels *= sim.num_geodes;
     }
-    println!("{}", levels);
+    Ok(levels.into())
 }
 
-pub fn day19(input: &str) { 
-    println!("{}", "Day 19:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
+pub struct Day19;
+
+impl Solution for Day19 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Vec<Blueprint>>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<Vec<Blueprint>>()) }
+    fn params(&self) -> Vec<(String, i64)> { Params::defaults() }
+
+    fn solve_with(&self, part: Part, parsed: &Parsed, overrides: &Overrides) -> Result<Answer> {
+        let params = Params::with_overrides(DAY, overrides)?;
+        match part {
+            Part::One => part1_with(parsed.get::<Vec<Blueprint>>(), &params),
+            Part::Two => part2_with(parsed.get::<Vec<Blueprint>>(), &params),
+        }
+    }
+}
@@ -1,38 +1,49 @@
-use std::time::Instant;
-use colored::Colorize;
+use crate::{answer::Answer, error::Result, parse::Lines, solution::{Parsed, Solution}};
 
-pub fn part1(input: &str) {
+const DAY: u32 = 2;
+
+/// Each round as the index of the opponent's letter and of the second column's letter
+pub fn parse(input: &str) -> Result<Vec<(u8, u8)>> {
+    Lines::new(DAY, input)
+        .map(|line| line.parse(|c| {
+            let theirs = c.one_of("ABC")? as u8 - b'A';
+            c.tag(" ")?;
+            let second = c.one_of("XYZ")? as u8 - b'X';
+            Ok((theirs, second))
+        }))
+        .collect()
+}
+
+pub fn part1(rounds: &[(u8, u8)]) -> Result<Answer> {
     let mut score = 0;
-    for s in input.split("\n") {
-        let (theirs, yours) = ((s.as_bytes()[0] - 65) as u32, (s.as_bytes()[2] - 88) as u32);
+    for &(theirs, yours) in rounds {
+        let (theirs, yours) = (theirs as u32, yours as u32);
         score += yours + 1; 
         if yours == theirs { score += 3; }
         else if yours == (theirs + 1) % 3 { score += 6 }
     }
-    println!("{score}");
+    Ok(score.into())
 }
 
-pub fn part2(input: &str) {
+pub fn part2(rounds: &[(u8, u8)]) -> Result<Answer> {
     let mut score = 0;
-    for s in input.split("\n") {
-        let (theirs, end) = ((s.as_bytes()[0] - 65) as i32, s.as_bytes()[2] as i32 - 89);
+    for &(theirs, end) in rounds {
+        let (theirs, end) = (theirs as i32, end as i32 - 1);
         let mut yours = (theirs + end) % 3;
         if yours == -1 { yours = 2}
         score += yours + 1; 
         if yours == theirs { score += 3; }
         else if yours == (theirs + 1) % 3 { score += 6 }
     }
-    println!("{score}");
+    Ok(score.into())
 }
 
-pub fn day2(input: &str) {
-    println!("{}", "Day 2:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Vec<(u8, u8)>>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<Vec<(u8, u8)>>()) }
+}
@@ -1,8 +1,10 @@
-use std::{fmt::{Display, Debug}, time::Instant};
-use colored::Colorize;
+use crate::{answer::Answer, error::{AocError, Result}, params::{Overrides, ParamSet}, parse::Lines, solution::{Parsed, Part, Solution}};
+use std::{fmt::{Display, Debug}};
+
+const DAY: u32 = 20;
 
 #[derive(Clone, Copy)]
-struct Coord {
+pub struct Coord {
     pub val: i128,
     pub original_position: usize,
 }
@@ -18,18 +20,16 @@ impl Debug for Coord {
     }
 }
 
-fn make_coords(input: &str) -> Vec<Coord> {
+pub fn parse(input: &str) -> Result<Vec<Coord>> {
     let mut coords = Vec::new();
-    let mut pos = 0;
-    for line in input.split("\n") {
-        let val = line.parse().unwrap();
+    for (pos, line) in Lines::new(DAY, input).enumerate() {
+        let val = line.parse(|c| c.int())?;
         coords.push(Coord {
             val,
             original_position: pos,
         });
-        pos += 1;
     }
-    coords
+    Ok(coords)
 }
 
 fn mix_coords(coords: &mut Vec<Coord>) {
@@ -51,26 +51,34 @@ fn mix_coords(coords: &mut Vec<Coord>) {
     }
 }
 
-pub fn part1(input: &str) {
-    let mut coords = make_coords(input);
+pub fn part1(coords: &[Coord]) -> Result<Answer> {
+    let mut coords = coords.to_vec();
     mix_coords(&mut coords);
     //println!("{:?}", coords);
     let idxs = [1000, 2000, 3000];
-    let base = coords.iter().position(|coord| coord.val == 0).unwrap();
+    let base = coords.iter().position(|coord| coord.val == 0)
+        .ok_or_else(|| AocError::no_answer(DAY, "there is no 0 in the file"))?;
     let mut sum = 0;
     for val in idxs {
         let val = (val + base) % coords.len();
         sum += coords[val].val;
     }
-    println!("{}", sum);
+    Ok(sum.into())
 }
 
-const KEY: i128 = 811589153;
+crate::params! {
+    /// The decryption key every number is multiplied by in part 2
+    key: i128 = 811589153,
+}
 
-pub fn part2(input: &str) {
-    let mut coords = make_coords(input);
+pub fn part2(coords: &[Coord]) -> Result<Answer> {
+    part2_with(coords, &Params::default())
+}
+
+pub fn part2_with(coords: &[Coord], params: &Params) -> Result<Answer> {
+    let mut coords = coords.to_vec();
     for coord in coords.iter_mut() {
-        coord.val *= KEY;
+        coord.val *= params.key;
     }
     for _ in 0..10 { 
         mix_coords(&mut coords); 
@@ -78,23 +86,31 @@ pub fn part2(input: &str) {
     }
     
     let idxs = [1000, 2000, 3000];
-    let base = coords.iter().position(|coord| coord.val == 0).unwrap();
+    let base = coords.iter().position(|coord| coord.val == 0)
+        .ok_or_else(|| AocError::no_answer(DAY, "there is no 0 in the file"))?;
     let mut sum = 0;
     for val in idxs {
         let val = (val + base) % coords.len();
         sum += coords[val].val;
     }
-    println!("{}", sum);
+    Ok(sum.into())
 }
 
-pub fn day20(input: &str) {
-    println!("{}", "Day 20:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
+pub struct Day20;
+
+impl Solution for Day20 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Vec<Coord>>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<Vec<Coord>>()) }
+    fn params(&self) -> Vec<(String, i64)> { Params::defaults() }
+
+    fn solve_with(&self, part: Part, parsed: &Parsed, overrides: &Overrides) -> Result<Answer> {
+        let params = Params::with_overrides(DAY, overrides)?;
+        match part {
+            Part::One => part1(parsed.get::<Vec<Coord>>()),
+            Part::Two => part2_with(parsed.get::<Vec<Coord>>(), &params),
+        }
+    }
+}
@@ -1,110 +1,124 @@
-use std::{collections::HashMap, rc::Rc, time::Instant};
-use colored::Colorize;
+use crate::{answer::Answer, error::Result, parse::{Line, Lines}, solution::{Parsed, Solution}};
+use std::{collections::HashMap, rc::Rc};
 
-struct Action<'a> {
-    lhs: &'a str,
-    rhs: &'a str,
-    op: fn(i64, i64) -> i64,
+const DAY: u32 = 21;
+
+struct Action {
+    lhs: String,
+    rhs: String,
+    op: Op,
 }
 
-fn add(a: i64, b: i64) -> i64 { a + b }
-fn sub(a: i64, b: i64) -> i64 { a - b }
-fn mul(a: i64, b: i64) -> i64 { a * b }
-fn div(a: i64, b: i64) -> i64 { a / b }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
 
-fn reverse_op(op: fn(i64, i64) -> i64) -> fn(i64, i64) -> i64 {
-    if op == add {
-        sub
-    }
-    else if op == sub {
-        add
-    }
-    else if op == mul {
-        div
+impl Op {
+    fn apply(self, a: i64, b: i64) -> i64 {
+        match self {
+            Op::Add => a + b,
+            Op::Sub => a - b,
+            Op::Mul => a * b,
+            Op::Div => a / b,
+        }
     }
-    else if op == div {
-        mul
+}
+
+fn reverse_op(op: Op) -> Op {
+    match op {
+        Op::Add => Op::Sub,
+        Op::Sub => Op::Add,
+        Op::Mul => Op::Div,
+        Op::Div => Op::Mul,
     }
-    else{ panic!() }
 }
 
-fn is_associative(op: fn(i64, i64) -> i64) -> bool {
-    if op == add || op == mul { true }
-    else if op == sub || op == div { false }
-    else { panic!() }
+fn is_associative(op: Op) -> bool {
+    matches!(op, Op::Add | Op::Mul)
 }
 
-fn _print_op(op: fn(i64, i64) -> i64) -> &'static str {
-    if op == add {
-        "+"
-    }
-    else if op == sub {
-        "-"
+fn _print_op(op: Op) -> &'static str {
+    match op {
+        Op::Add => "+",
+        Op::Sub => "-",
+        Op::Mul => "*",
+        Op::Div => "/",
     }
-    else if op == mul {
-        "*"
-    }
-    else if op == div {
-        "/"
-    }
-    else{ panic!() }
 }
 
-fn resolve_monkey<'a, 'b>(name: &'a str, monkeys: &'b HashMap<&'a str, Action<'a>>, resolved: &'b mut HashMap<&'a str, i64>) -> i64 {
+fn resolve_monkey<'a>(name: &'a str, monkeys: &'a HashMap<String, Action>, resolved: &mut HashMap<&'a str, i64>) -> i64 {
     if resolved.contains_key(name) {
        *&resolved[name]
     }
     else {
         let action = &monkeys[name];
-        let lhs = resolve_monkey(action.lhs, monkeys, resolved);
-        let rhs = resolve_monkey(action.rhs, monkeys, resolved);
+        let lhs = resolve_monkey(&action.lhs, monkeys, resolved);
+        let rhs = resolve_monkey(&action.rhs, monkeys, resolved);
 
-        let result = (action.op)(lhs, rhs);
+        let result = action.op.apply(lhs, rhs);
         resolved.insert(name, result);
         result
     }
 }
 
-fn create_initial_setup(input: &str) -> (HashMap<&str, Action>, HashMap<&str, i64>) {
+enum Job {
+    Number(i64),
+    Op(Action),
+}
+
+/// Reads a `name: 5` or `name: lhs + rhs` line
+fn parse_job(line: Line<'_>) -> Result<(&str, Job)> {
+    line.parse(|c| {
+        let name = c.word()?;
+        c.tag(": ")?;
+        if c.rest().starts_with(|c: char| c.is_ascii_digit() || c == '-') {
+            return Ok((name, Job::Number(c.int()?)));
+        }
+        let lhs = c.word()?;
+        c.tag(" ")?;
+        let op = match c.one_of("+-*/")? {
+            '+' => Op::Add,
+            '-' => Op::Sub,
+            '*' => Op::Mul,
+            _ => Op::Div,
+        };
+        c.tag(" ")?;
+        let rhs = c.word()?;
+        Ok((name, Job::Op(Action { lhs: lhs.to_string(), rhs: rhs.to_string(), op })))
+    })
+}
+
+/// Every monkey's job, split into the ones waiting on other monkeys and the ones with a number
+pub struct Jobs {
+    monkeys: HashMap<String, Action>,
+    numbers: HashMap<String, i64>,
+}
+
+pub fn parse(input: &str) -> Result<Jobs> {
     let mut monkeys = HashMap::new();
     let mut map = HashMap::new();
-    for line in input.split("\n") {
-        let name = &line[0..4];
-        let rest = line.split(": ").skip(1).next().unwrap();
-        if let Ok(num) = rest.parse() {
-            map.insert(name, num);
-        } 
-        else {
-            let lhs = &rest[..4];
-            let rhs = &rest[(rest.len() - 4)..];
-            let op = &rest[5..6];
-            let func = match op {
-                "+" => {
-                    add
-                },
-                "-" => {
-                    sub
-                },
-                "*" => {
-                    mul
-                },
-                "/" => {
-                    div
-                },
-                _ => unreachable!(),
-            };
-            let action = Action { lhs, rhs, op: func };
-            monkeys.insert(name, action);
+    for line in Lines::new(DAY, input) {
+        match parse_job(line)? {
+            (name, Job::Number(num)) => {
+                map.insert(name.to_string(), num);
+            },
+            (name, Job::Op(action)) => {
+                monkeys.insert(name.to_string(), action);
+            },
         }
     }
-    (monkeys, map)
+    Ok(Jobs { monkeys, numbers: map })
 }
 
 #[derive(Debug, Clone)]
 struct HumnAction {
     lhs: HumnResult,
     rhs: HumnResult,
-    op: fn(i64, i64) -> i64,
+    op: Op,
 } 
 
 impl HumnAction {
@@ -112,7 +126,7 @@ impl HumnAction {
         if let HumnResult::Literal(lhs) = self.lhs 
         && let HumnResult::Literal(rhs) = self.rhs 
         {
-            Some((self.op)(lhs, rhs))
+            Some(self.op.apply(lhs, rhs))
         } else { None }
     }
 
@@ -147,7 +161,7 @@ impl HumnAction {
 
             _ => panic!("Illegal humn resolve state"),
         };
-        (self.op)(lhs, rhs)
+        self.op.apply(lhs, rhs)
     }
 }
 
@@ -158,57 +172,31 @@ enum HumnResult {
     Humn,
 }
 
-fn create_initial_setup_humn(input: &str) -> (HashMap<&str, Action>, HashMap<&str, Rc<HumnAction>>) {
-    let mut monkeys = HashMap::new();
+/// The monkeys with a number, apart from `humn` since that's the number part 2 is looking for
+fn humn_literals(jobs: &Jobs) -> HashMap<&str, Rc<HumnAction>> {
     let mut literals = HashMap::new();
-    for line in input.split("\n") {
-        let name = &line[0..4];
-        let rest = line.split(": ").skip(1).next().unwrap();
-        if name == "humn" {}
-        else if let Ok(num) = rest.parse() {
-            let action = HumnAction {
-                lhs: HumnResult::Literal(num),
-                rhs: HumnResult::Literal(0_i64),
-                op: add,
-            };
+    for (name, &num) in &jobs.numbers {
+        if name == "humn" { continue; }
+        let action = HumnAction {
+            lhs: HumnResult::Literal(num),
+            rhs: HumnResult::Literal(0_i64),
+            op: Op::Add,
+        };
 
-            literals.insert(name, Rc::new(action));
-        } 
-        else {
-            let lhs = &rest[..4];
-            let rhs = &rest[(rest.len() - 4)..];
-            let op = &rest[5..6];
-            let func = match op {
-                "+" => {
-                    add
-                },
-                "-" => {
-                    sub
-                },
-                "*" => {
-                    mul
-                },
-                "/" => {
-                    div
-                },
-                _ => unreachable!(),
-            };
-            let action = Action { lhs, rhs, op: func };
-            monkeys.insert(name, action);
-        }
+        literals.insert(name.as_str(), Rc::new(action));
     }
-    (monkeys, literals)
+    literals
 }
 
-fn create_humn_chain<'a, 'b>(name: &'a str, monkeys: &'b HashMap<&'a str, Action<'a>>, 
-    resolved: &'b mut HashMap<&'a str, Rc<HumnAction>>) -> Rc<HumnAction> {
+fn create_humn_chain<'a>(name: &'a str, monkeys: &'a HashMap<String, Action>, 
+    resolved: &mut HashMap<&'a str, Rc<HumnAction>>) -> Rc<HumnAction> {
         if resolved.contains_key(name) {
             resolved[name].clone()
         }
         else {
             let action = &monkeys[name];
-            let lhs = if resolved.contains_key(action.lhs) {
-                let depend = resolved[action.lhs].clone();
+            let lhs = if resolved.contains_key(action.lhs.as_str()) {
+                let depend = resolved[action.lhs.as_str()].clone();
                 if let Some(res) = depend.resolve_literal() {
                     HumnResult::Literal(res)
                 }
@@ -218,7 +206,7 @@ fn create_humn_chain<'a, 'b>(name: &'a str, monkeys: &'b HashMap<&'a str, Action
             } else if action.lhs == "humn" {
                 HumnResult::Humn
             } else {
-                let res = create_humn_chain(action.lhs, monkeys, resolved);
+                let res = create_humn_chain(&action.lhs, monkeys, resolved);
                 if let Some(res) = res.resolve_literal() {
                     HumnResult::Literal(res)
                 }
@@ -227,8 +215,8 @@ fn create_humn_chain<'a, 'b>(name: &'a str, monkeys: &'b HashMap<&'a str, Action
                 }
             };
 
-            let rhs = if resolved.contains_key(action.rhs) {
-                let depend = resolved[action.rhs].clone();
+            let rhs = if resolved.contains_key(action.rhs.as_str()) {
+                let depend = resolved[action.rhs.as_str()].clone();
                 if let Some(res) = depend.resolve_literal() {
                     HumnResult::Literal(res)
                 }
@@ -238,7 +226,7 @@ fn create_humn_chain<'a, 'b>(name: &'a str, monkeys: &'b HashMap<&'a str, Action
             } else if action.rhs == "humn" {
                 HumnResult::Humn
             } else {
-                let res = create_humn_chain(action.rhs, monkeys, resolved);
+                let res = create_humn_chain(&action.rhs, monkeys, resolved);
                 if let Some(res) = res.resolve_literal() {
                     HumnResult::Literal(res)
                 }
@@ -250,7 +238,7 @@ fn create_humn_chain<'a, 'b>(name: &'a str, monkeys: &'b HashMap<&'a str, Action
             let rc = Rc::new(HumnAction {
                 lhs,
                 rhs,
-                op: action.op.clone(),
+                op: action.op,
             });
             resolved.insert(name, rc.clone());
             rc
@@ -273,29 +261,29 @@ fn is_tree_solvable(root: &Rc<HumnAction>) -> bool {
 fn solve_tree(root: &Rc<HumnAction>, val: i64) -> i64 {
     match (&root.lhs, &root.rhs) {
         (HumnResult::Dependent(new_root), HumnResult::Literal(rhs)) => {
-            let true_val = solve_tree(new_root, (reverse_op(root.op))(val, *rhs));
+            let true_val = solve_tree(new_root, reverse_op(root.op).apply(val, *rhs));
             true_val
         },
         (HumnResult::Literal(lhs), HumnResult::Dependent(new_root)) => {
             let true_val = if is_associative(root.op) {
-                solve_tree(new_root, (reverse_op(root.op))(val, *lhs))
+                solve_tree(new_root, reverse_op(root.op).apply(val, *lhs))
             }
             else {
-                solve_tree(new_root, (root.op)(*lhs, val))
+                solve_tree(new_root, root.op.apply(*lhs, val))
             };
 
             true_val
         },
         (HumnResult::Humn, HumnResult::Literal(rhs)) => {
-            let true_val = (reverse_op(root.op))(val, *rhs);
+            let true_val = reverse_op(root.op).apply(val, *rhs);
             true_val
         },
         (HumnResult::Literal(lhs), HumnResult::Humn) => {
             let true_val = if is_associative(root.op) {
-                (reverse_op(root.op))(val, *lhs)
+                reverse_op(root.op).apply(val, *lhs)
             }
             else {
-                reverse_op(root.op)(*lhs, val)
+                reverse_op(root.op).apply(*lhs, val)
             };
             true_val
         },
@@ -303,15 +291,15 @@ fn solve_tree(root: &Rc<HumnAction>, val: i64) -> i64 {
     }
 }
 
-pub fn part1(input: &str) {
-    let (monkeys, mut resolved) = create_initial_setup(input);
-    println!("{}", resolve_monkey("root", &monkeys, &mut resolved));
+pub fn part1(jobs: &Jobs) -> Result<Answer> {
+    let mut resolved = jobs.numbers.iter().map(|(name, &num)| (name.as_str(), num)).collect();
+    Ok(resolve_monkey("root", &jobs.monkeys, &mut resolved).into())
 
 }
 
-pub fn part2(input: &str) {
-    let (monkeys, mut resolved) = create_initial_setup_humn(input);
-    let root = create_humn_chain("root", &monkeys, &mut resolved);
+pub fn part2(jobs: &Jobs) -> Result<Answer> {
+    let mut resolved = humn_literals(jobs);
+    let root = create_humn_chain("root", &jobs.monkeys, &mut resolved);
     //println!("{:#?}", root);
     assert!(is_tree_solvable(&root));
 
@@ -331,19 +319,17 @@ pub fn part2(input: &str) {
         panic!();
     };
 
-    println!("{}", humn);
+    Ok(humn.into())
 
 
 }
 
-pub fn day21(input: &str) {
-    println!("{}", "Day 21:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
+pub struct Day21;
+
+impl Solution for Day21 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Jobs>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<Jobs>()) }
+}
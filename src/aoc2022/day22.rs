@@ -1,55 +1,41 @@
-use std::{ops::{Index, IndexMut}, time::Instant};
-use colored::Colorize;
+use std::collections::BTreeMap;
 
-#[derive(Debug, Clone)]
-struct Row {
-    pub row: Vec<char>,
-    begin: usize,
-}
+use crate::{answer::Answer, error::{AocError, Result}, params::{Overrides, ParamSet}, parse::{Line, Lines}, solution::{Parsed, Part, Solution},
+    utils::{grid::{Grid, Pos}, Direction, Point3}};
 
-impl Row {
-    fn coord_in_bounds(&self, coord: usize) -> bool {
-        self.begin <= coord && self.begin + self.row.len() > coord
-    }
-}
+const DAY: u32 = 22;
 
-impl Index<usize> for Row {
-    type Output = char;
-
-    fn index(&self, index: usize) -> &Self::Output {
-        assert!(index >= self.begin);
-        &self.row[index - self.begin]
-    }
+/// Whether `pos` is on the board, rather than in the space around it
+fn on_map(map: &Grid<char>, pos: (usize, usize)) -> bool {
+    map.get(pos).is_some_and(|&c| c != ' ')
 }
 
-impl IndexMut<usize> for Row {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        assert!(index >= self.begin);
-        &mut self.row[index - self.begin]
-    }
+/// The map of the board and the path to follow over it
+pub struct Notes {
+    map: Grid<char>,
+    directions: Vec<Move>,
 }
 
-fn read_rows(input: &str) -> Vec<Row> {
+/// Reads the rows of the map, then after a blank line the path to follow
+pub fn parse(input: &str) -> Result<Notes> {
+    let mut lines = Lines::new(DAY, input);
     let mut rows = Vec::new();
-    for line in input.split("\n") {
-        if line.trim().is_empty() { break; }
-        let mut row = Vec::new();
-        let mut begin = 0;
-        for c in line.chars() {
-            if c == ' ' {
-                if !row.is_empty() { break; }
-                else {
-                    begin += 1;
-                }
-            }
-            else {
-                row.push(c);
+    loop {
+        let line = lines.expect(if rows.is_empty() { "a row of the map" } else { "a blank line" })?;
+        if line.text.trim().is_empty() {
+            if rows.is_empty() {
+                return Err(line.error_at(line.text.len() + 1, "'.' or '#'"));
             }
+            break;
         }
-        rows.push(Row { row, begin})
+        rows.push(line);
     }
-    rows
-
+    let map = Grid::from_ragged_lines(rows, ' ', |c| matches!(c, '.' | '#' | ' ').then_some(c), "'.', '#' or ' '")?;
+    let directions = parse_directions(lines.expect("the path")?)?;
+    if let Some(extra) = lines.next() {
+        return Err(extra.error_at(1, "end of input"));
+    }
+    Ok(Notes { map, directions })
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -59,95 +45,44 @@ enum Move {
     Straight(usize),
 }
 
-fn parse_directions(mut directions: &str) -> Vec<Move> {
-    let mut moves = Vec::new();
-    let mut current_loc = 0;
-    while current_loc < directions.len() {
-        let current_char = directions[current_loc..current_loc + 1].chars().next().unwrap();
-        match current_char {
-            'L' => {
-                if current_loc > 0 {
-                    let num = directions[0..current_loc].parse().unwrap();
-                    moves.push(Move::Straight(num));
-                }
-                moves.push(Move::Left);
-                directions = &directions[current_loc + 1..];
-                current_loc = 0;
-
-            },
-            'R' => {
-                if current_loc > 0 {
-                    let num = directions[0..current_loc].parse().unwrap();
-                    moves.push(Move::Straight(num));
-                }
-                moves.push(Move::Right);
-                directions = &directions[current_loc + 1..];
-                current_loc = 0;
-            },
-            _ => {
-                current_loc += 1;
+fn parse_directions(line: Line) -> Result<Vec<Move>> {
+    line.parse(|c| {
+        let mut moves = Vec::new();
+        while !c.is_empty() {
+            if c.rest().starts_with(|c: char| c.is_ascii_digit()) {
+                moves.push(Move::Straight(c.int()?));
+            } else {
+                moves.push(match c.one_of("LR")? {
+                    'L' => Move::Left,
+                    _ => Move::Right,
+                });
             }
         }
+        Ok(moves)
+    })
+}
+
+/// The position one step `dir` from `pos`, wrapping round to the far side of the board if it
+/// steps off it
+fn next_position(map: &Grid<char>, pos: Pos, dir: Direction) -> Pos {
+    match map.step(pos, dir.offset()).filter(|&next| on_map(map, next)) {
+        Some(next) => next,
+        None => map.ray(pos, dir.turn_around().offset()).take_while(|&back| on_map(map, back)).last().unwrap_or(pos),
     }
-    
-    if current_loc > 0 {
-        let num = directions[0..current_loc].parse().unwrap();
-        moves.push(Move::Straight(num));
+}
+
+/// The password's value for facing `dir`
+fn facing(dir: Direction) -> usize {
+    match dir {
+        Direction::Right => 0,
+        Direction::Down => 1,
+        Direction::Left => 2,
+        Direction::Up => 3,
     }
-    moves
 }
 
-// Right, Down, Left, Up
-fn right(pos: (usize, usize)) -> (isize, isize) { (pos.0 as isize, pos.1 as isize + 1) }
-fn down(pos: (usize, usize)) -> (isize, isize) { (pos.0 as isize + 1, pos.1 as isize) }
-fn left(pos: (usize, usize)) -> (isize, isize) { (pos.0 as isize, pos.1 as isize - 1) }
-fn up(pos: (usize, usize)) -> (isize, isize) { (pos.0 as isize - 1, pos.1 as isize) }
-
-static FACING_OFFSETS: &[fn((usize, usize)) -> (isize, isize); 4] = &[right, down, left, up];
-
-static RIGHT: usize = 0; 
-static DOWN: usize = 1;
-static LEFT: usize = 2;
-static UP: usize = 3;
-
-fn find_next_col(col: isize, original: (usize, usize), map: &Vec<Row>, offset: isize) -> usize {
-    if col < 0 || 
-    !map[original.0].coord_in_bounds(col as usize)
-    {
-        let mut new_col = original.1 as isize;
-        while new_col >= 0 &&
-        map[original.0].coord_in_bounds(new_col as usize)
-        { new_col += offset };
-        new_col -= offset;
-        new_col as usize
-    } else { col as usize }
-} 
-
-fn find_next_row(row: isize, original: (usize, usize), map: &Vec<Row>, offset: isize) -> usize {
-    if row < 0 || 
-    (row as usize) >= map.len() ||
-    !map[row as usize].coord_in_bounds(original.1)
-    {
-        let mut new_row = original.0 as isize;
-        while new_row >= 0 && 
-        (new_row as usize) < map.len() && 
-        map[new_row as usize].coord_in_bounds(original.1) 
-        { new_row += offset};
-        new_row -= offset;
-        new_row as usize
-    } else { row as usize }
-} 
-
-fn debug_movement(pos: (usize, usize), map: &mut Vec<Row>, move_idx: usize) {    
-    let new_c = match move_idx {
-        0 => '>',
-        1 => 'v',
-        2 => '<',
-        3 => '^',
-        _ => unreachable!(),
-    };
-    
-    map[pos.0][pos.1] = new_c;
+fn debug_movement(pos: Pos, map: &mut Grid<char>, dir: Direction) {
+    map[pos] = dir.arrow();
 }
 
 #[derive(PartialEq, Eq, Clone, Copy)]
@@ -160,8 +95,8 @@ enum ConnectionType {
 struct Edge {
     span1: ((usize, usize), (usize, usize)),    
     span2: ((usize, usize), (usize, usize)),
-    span1_entrance_direction: usize,
-    span2_entrance_direction: usize,
+    span1_entrance_direction: Direction,
+    span2_entrance_direction: Direction,
     ty: ConnectionType
 }
 
@@ -183,7 +118,7 @@ fn point_is_within_span(span: ((usize, usize), (usize, usize)), point: (usize, u
 }
 
 impl Edge {
-    fn try_map_point(&self, point: (usize, usize), is_vertical: bool) -> Option<((usize, usize), usize)> {
+    fn try_map_point(&self, point: (usize, usize), is_vertical: bool) -> Option<((usize, usize), Direction)> {
         let (this_edge, other_edge, direction) = 
         if point_is_within_span(self.span1, point) && (span_is_vert(self.span1) != !is_vertical) {
             (self.span1, self.span2, self.span2_entrance_direction)
@@ -221,96 +156,51 @@ impl Edge {
     }
 }
 
-fn find_next_col_with_edges(col: isize, original: (usize, usize), edges: &Vec<Edge>, 
-    map: &Vec<Row>, original_direction: usize) -> ((usize, usize), usize) 
-{
-    if col < 0 || 
-    !map[original.0].coord_in_bounds(col as usize)
-    {
-        for edge in edges {
-            if let Some((new_point, direction)) = edge.try_map_point(original, true) { 
-                return (new_point, direction);
-            }
-        }
-        println!("Didn't find an edge to map point!");
-        println!("Original coords: {:?}, new column: {}", original, col);
-        panic!();
-    }
-    else {
-        ((original.0, col as usize), original_direction)
+/// The position and facing one step `dir` from `pos`, following `edges` round the cube if it
+/// steps off the board
+fn next_with_edges(pos: Pos, dir: Direction, edges: &[Edge], map: &Grid<char>) -> (Pos, Direction) {
+    match map.step(pos, dir.offset()).filter(|&next| on_map(map, next)) {
+        Some(next) => (next, dir),
+        None => edges.iter()
+            .find_map(|edge| edge.try_map_point(pos, dir.is_horizontal()))
+            .unwrap_or_else(|| panic!("Didn't find an edge to map point!\nOriginal coords: {:?}, facing: {:?}", pos, dir)),
     }
-} 
-
-fn find_next_row_with_edges(row: isize, original: (usize, usize), edges: &Vec<Edge>, 
-    map: &Vec<Row>, original_direction: usize) -> ((usize, usize), usize) 
-{
-    if row < 0 || 
-    (row as usize) >= map.len() ||
-    !map[row as usize].coord_in_bounds(original.1)
-    {
-        for edge in edges {
-            if let Some((new_point, direction)) = edge.try_map_point(original, false) {
-                return (new_point, direction);
-            }
-        }
-        println!("Didn't find an edge to map point!");
-        println!("Original coords: {:?}, new row: {}", original, row);
-        panic!();
-    }
-    else {
-        ((row as usize, original.1), original_direction)
-    }
-} 
-
+}
 
-pub fn part1(input: &str) {
-    let map = read_rows(input);
+pub fn part1(notes: &Notes) -> Result<Answer> {
+    let Notes { map, directions } = notes;
 
     let mut movement_map = map.clone();
 
-    let len = input.split("\n").count();
-    let directions = input.split("\n").skip(len - 1).next().unwrap();
-    let directions = parse_directions(directions);
-
     //println!("{:?}", directions);
     
-    let mut position = (0, map[0].begin); // Row, col
+    let mut position = (0, map.row(0).iter().position(|&c| c != ' ').unwrap()); // Row, col
     // Find actual position
-    while map[0][position.1] == '#' {
+    while map[position] == '#' {
         position.1 += 1;
     }
 
-    let mut move_idx = 0;
+    let mut dir = Direction::Right;
 
-    for mv in &directions {
+    for mv in directions {
         match mv {
             Move::Left => {
-                debug_movement(position, &mut movement_map, move_idx);
+                debug_movement(position, &mut movement_map, dir);
 
-                if move_idx == 0 { move_idx = FACING_OFFSETS.len(); }
-                move_idx -= 1;
+                dir = dir.turn_left();
             },
             Move::Right => {
-                debug_movement(position, &mut movement_map, move_idx);
+                debug_movement(position, &mut movement_map, dir);
 
-                move_idx += 1;
-                move_idx %= FACING_OFFSETS.len();
+                dir = dir.turn_right();
             },
             Move::Straight(amount) => {
                 for _ in 0..*amount {
-                    debug_movement(position, &mut movement_map, move_idx);
-
-                    let next = {
-                        if move_idx % 2 == 1 {
-                            let (row, col) = (FACING_OFFSETS[move_idx])(position);
-                            (find_next_row(row, position, &map, (move_idx as isize) - 2), col as usize)
-                        } else {
-                            let (row, col) = (FACING_OFFSETS[move_idx])(position);
-                            (row as usize, find_next_col(col, position, &map, (move_idx as isize) - 1 ))
-                        }
-                    };
+                    debug_movement(position, &mut movement_map, dir);
+
+                    let next = next_position(map, position, dir);
                     // If we've hit a wall, just stop
-                    if map[next.0][next.1] == '#' {
+                    if map[next] == '#' {
                         break;
                     }
                     else {
@@ -322,121 +212,179 @@ pub fn part1(input: &str) {
         }
     }
     
-    let pwd = 1000 * (position.0 + 1) + 4 * (position.1 + 1) + move_idx;
-    println!("{}", pwd);
+    let pwd = 1000 * (position.0 + 1) + 4 * (position.1 + 1) + facing(dir);
+    Ok(pwd.into())
+
+}
 
+crate::params! {
+    /// How wide each face of the cube is (4 in the example)
+    chunk_size: usize [2..] = 50,
 }
 
-// True layout
-static CHUNK_SIZE: usize = 50;
-
-static EDGE_A: Edge = Edge {
-    span1: ((CHUNK_SIZE * 3 - 1, CHUNK_SIZE), (CHUNK_SIZE * 3 - 1, CHUNK_SIZE * 2 - 1)),
-    span2: ((CHUNK_SIZE * 3 , CHUNK_SIZE - 1), (CHUNK_SIZE * 4 - 1, CHUNK_SIZE - 1)),
-    span1_entrance_direction: UP,
-    span2_entrance_direction: LEFT,
-    ty: ConnectionType::Straight,
-};
-static EDGE_B: Edge = Edge {
-    span1: ((CHUNK_SIZE, CHUNK_SIZE), (CHUNK_SIZE * 2 - 1, CHUNK_SIZE)),
-    span2: ((CHUNK_SIZE * 2, 0), (CHUNK_SIZE * 2, CHUNK_SIZE - 1)),
-    span1_entrance_direction: RIGHT,
-    span2_entrance_direction: DOWN,
-    ty: ConnectionType::Straight,
-};
-static EDGE_C: Edge = Edge {
-    span1: ((0, CHUNK_SIZE), (CHUNK_SIZE - 1, CHUNK_SIZE)),
-    span2: ((CHUNK_SIZE * 2, 0), (CHUNK_SIZE * 3 - 1, 0)),
-    span1_entrance_direction: RIGHT,
-    span2_entrance_direction: RIGHT,
-    ty: ConnectionType::Reversed,
-};
-static EDGE_D: Edge = Edge {
-    span1: ((0, CHUNK_SIZE), (0, CHUNK_SIZE * 2 - 1)),
-    span2: ((CHUNK_SIZE * 3, 0), (CHUNK_SIZE * 4 - 1, 0)),
-    span1_entrance_direction: DOWN,
-    span2_entrance_direction: RIGHT,
-    ty: ConnectionType::Straight,
-};
-static EDGE_E: Edge = Edge {
-    span1: ((0, CHUNK_SIZE * 2), (0, CHUNK_SIZE * 3 - 1)),
-    span2: ((CHUNK_SIZE * 4 - 1, 0), (CHUNK_SIZE * 4 - 1, CHUNK_SIZE - 1)),
-    span1_entrance_direction: DOWN,
-    span2_entrance_direction: UP,
-    ty: ConnectionType::Straight
-};
-static EDGE_F: Edge = Edge {
-    span1: ((CHUNK_SIZE - 1, CHUNK_SIZE * 2), (CHUNK_SIZE - 1, CHUNK_SIZE * 3 - 1)),
-    span2: ((CHUNK_SIZE, CHUNK_SIZE * 2 - 1), (CHUNK_SIZE * 2 - 1, CHUNK_SIZE * 2 - 1)),
-    span1_entrance_direction: UP,
-    span2_entrance_direction: LEFT,
-    ty: ConnectionType::Straight,
-};
-static EDGE_G: Edge = Edge {
-    span1: ((0, CHUNK_SIZE * 3 - 1), (CHUNK_SIZE - 1, CHUNK_SIZE * 3 - 1)),
-    span2: ((CHUNK_SIZE * 2, CHUNK_SIZE * 2 - 1), (CHUNK_SIZE * 3 - 1, CHUNK_SIZE * 2 - 1)),
-    span1_entrance_direction: LEFT,
-    span2_entrance_direction: LEFT,
-    ty: ConnectionType::Reversed,
-};
-
-
-pub fn part2(input: &str) {
-    let edges = vec![EDGE_A, EDGE_B, EDGE_C, EDGE_D, EDGE_E, EDGE_F, EDGE_G];
-
-    let map = read_rows(input);
+/// Which way a face of the cube points, and which ways its columns and rows run, once the
+/// map is folded up
+#[derive(Clone, Copy)]
+struct Orientation {
+    normal: Point3<i32>,
+    right: Point3<i32>,
+    down: Point3<i32>,
+}
+
+impl Orientation {
+    /// The orientation of the face next to this one on the map, `dir` of it, which folds
+    /// over this face's side
+    fn fold(self, dir: Direction) -> Orientation {
+        let Orientation { normal, right, down } = self;
+        match dir {
+            Direction::Up => Orientation { normal: -down, right, down: normal },
+            Direction::Right => Orientation { normal: right, right: -normal, down },
+            Direction::Down => Orientation { normal: down, right, down: -normal },
+            Direction::Left => Orientation { normal: -right, right: normal, down },
+        }
+    }
+
+    /// Which way the side `dir` of the face leads, which is the normal of the face it meets
+    fn side(self, dir: Direction) -> Point3<i32> {
+        match dir {
+            Direction::Up => -self.down,
+            Direction::Right => self.right,
+            Direction::Down => self.down,
+            Direction::Left => -self.right,
+        }
+    }
+
+    /// Which way the side `dir` of the face runs, from its first cell on the map to its last
+    fn along(self, dir: Direction) -> Point3<i32> {
+        if dir.is_horizontal() { self.down } else { self.right }
+    }
+}
+
+/// The cells along the side `dir` of the face at `face` on the grid of faces, from the top
+/// or left
+fn side_span(face: Pos, dir: Direction, chunk_size: usize) -> ((usize, usize), (usize, usize)) {
+    let (top, left) = (face.0 * chunk_size, face.1 * chunk_size);
+    let (bottom, right) = (top + chunk_size - 1, left + chunk_size - 1);
+    match dir {
+        Direction::Up => ((top, left), (top, right)),
+        Direction::Right => ((top, right), (bottom, right)),
+        Direction::Down => ((bottom, left), (bottom, right)),
+        Direction::Left => ((top, left), (bottom, left)),
+    }
+}
+
+/// How the faces of the cube join up, found by folding the map's faces, `chunk_size` wide,
+/// round the first one
+///
+/// Every side of a face that doesn't meet another on the map becomes an edge, joined to the
+/// side it touches once the cube is folded up.
+fn edges(map: &Grid<char>, chunk_size: usize) -> Result<Vec<Edge>> {
+    let not_a_net = || AocError::no_answer(DAY, format!("the map doesn't fold into a cube with faces {chunk_size} wide"));
+    if !map.height().is_multiple_of(chunk_size) || !map.width().is_multiple_of(chunk_size) {
+        return Err(not_a_net());
+    }
+    let faces = Grid::from_fn(map.height() / chunk_size, map.width() / chunk_size,
+        |(row, col)| on_map(map, (row * chunk_size, col * chunk_size)));
+    let first = faces.positions().find(|&face| faces[face]).ok_or_else(not_a_net)?;
+
+    let mut folded = BTreeMap::new();
+    folded.insert(first, Orientation { normal: Point3::new(0, 0, 1), right: Point3::new(1, 0, 0), down: Point3::new(0, 1, 0) });
+    let mut to_fold = vec![first];
+    while let Some(face) = to_fold.pop() {
+        let orientation = folded[&face];
+        for dir in Direction::ALL {
+            if let Some(next) = faces.step(face, dir.offset()).filter(|next| faces[*next] && !folded.contains_key(next)) {
+                folded.insert(next, orientation.fold(dir));
+                to_fold.push(next);
+            }
+        }
+    }
+    let mut normals = folded.values().map(|orientation| orientation.normal).collect::<Vec<_>>();
+    normals.sort();
+    normals.dedup();
+    if folded.len() != 6 || normals.len() != 6 || faces.iter().filter(|(_, face)| **face).count() != 6 {
+        return Err(not_a_net());
+    }
+
+    let mut edges = Vec::new();
+    for (&face, &orientation) in &folded {
+        for dir in Direction::ALL {
+            if faces.step(face, dir.offset()).is_some_and(|next| faces[next]) {
+                continue;
+            }
+            let (&other, &other_orientation) = folded.iter()
+                .find(|(_, other)| other.normal == orientation.side(dir))
+                .expect("every side of a cube meets another face");
+            let other_dir = Direction::ALL.into_iter()
+                .find(|&other_dir| other_orientation.side(other_dir) == orientation.normal)
+                .expect("the faces meet along one side");
+            // Each pair of sides is only added once, from whichever comes first
+            if (other, other_dir) < (face, dir) {
+                continue;
+            }
+            edges.push(Edge {
+                span1: side_span(face, dir, chunk_size),
+                span2: side_span(other, other_dir, chunk_size),
+                span1_entrance_direction: dir.turn_around(),
+                span2_entrance_direction: other_dir.turn_around(),
+                ty: if orientation.along(dir) == other_orientation.along(other_dir) {
+                    ConnectionType::Straight
+                } else {
+                    ConnectionType::Reversed
+                },
+            });
+        }
+    }
+    Ok(edges)
+}
+
+pub fn part2(notes: &Notes) -> Result<Answer> {
+    part2_with(notes, &Params::default())
+}
+
+/// Part 2 with cube faces `params.chunk_size` wide
+pub fn part2_with(notes: &Notes, params: &Params) -> Result<Answer> {
+    let edges = edges(&notes.map, params.chunk_size)?;
+
+    let Notes { map, directions } = notes;
 
     let mut movement_map = map.clone();
     let mut path = Vec::new();
-
-    let len = input.split("\n").count();
-    let directions = input.split("\n").skip(len - 1).next().unwrap();
-    let directions = parse_directions(directions);
     
-    let mut position = (0, map[0].begin); // Row, col
+    let mut position = (0, map.row(0).iter().position(|&c| c != ' ').unwrap()); // Row, col
     // Find actual position
-    while map[0][position.1] == '#' {
+    while map[position] == '#' {
         position.1 += 1;
     }
 
-    let mut move_idx = 0;
+    let mut dir = Direction::Right;
 
-    for mv in &directions {
+    for mv in directions {
         match mv {
             Move::Left => {
-                debug_movement(position, &mut movement_map, move_idx);
-                path.push((position, move_idx));
+                debug_movement(position, &mut movement_map, dir);
+                path.push((position, dir));
 
-                if move_idx == 0 { move_idx = FACING_OFFSETS.len(); }
-                move_idx -= 1;
+                dir = dir.turn_left();
             },
             Move::Right => {
-                debug_movement(position, &mut movement_map, move_idx);
-                path.push((position, move_idx));
+                debug_movement(position, &mut movement_map, dir);
+                path.push((position, dir));
 
-                move_idx += 1;
-                move_idx %= FACING_OFFSETS.len();
+                dir = dir.turn_right();
             },
             Move::Straight(amount) => {
                 for _count in 0..*amount {
-                    debug_movement(position, &mut movement_map, move_idx);
-                    path.push((position, move_idx));
-
-                    let (next, new_move_idx) = {
-                        if move_idx % 2 == 1 {
-                            let (row, _col) = (FACING_OFFSETS[move_idx])(position);
-                            find_next_row_with_edges(row, position, &edges, &map, move_idx)
-                        } else {
-                            let (_row, col) = (FACING_OFFSETS[move_idx])(position);
-                            find_next_col_with_edges(col, position, &edges, &map, move_idx)
-                        }
-                    };
+                    debug_movement(position, &mut movement_map, dir);
+                    path.push((position, dir));
+
+                    let (next, new_dir) = next_with_edges(position, dir, &edges, map);
                     // If we've hit a wall, just stop
-                    if map[next.0][next.1] == '#' {
+                    if map[next] == '#' {
                         break;
                     }
                     else {
-                        move_idx = new_move_idx;
+                        dir = new_dir;
                         position = next;
                     }
                 }
@@ -445,24 +393,31 @@ pub fn part2(input: &str) {
         }
     }
     
-    debug_movement(position, &mut movement_map, move_idx);
+    debug_movement(position, &mut movement_map, dir);
 
     
-    movement_map[position.0][position.1] = '*';
+    movement_map[position] = '*';
 
-    let pwd = 1000 * (position.0 + 1) + 4 * (position.1 + 1) + move_idx;
-    println!("{}", pwd);
+    let pwd = 1000 * (position.0 + 1) + 4 * (position.1 + 1) + facing(dir);
+    Ok(pwd.into())
 
 }
 
-pub fn day22(input: &str) {
-    println!("{}", "Day 22:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
+pub struct Day22;
+
+impl Solution for Day22 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Notes>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<Notes>()) }
+    fn params(&self) -> Vec<(String, i64)> { Params::defaults() }
+
+    fn solve_with(&self, part: Part, parsed: &Parsed, overrides: &Overrides) -> Result<Answer> {
+        let params = Params::with_overrides(DAY, overrides)?;
+        match part {
+            Part::One => part1(parsed.get::<Notes>()),
+            Part::Two => part2_with(parsed.get::<Notes>(), &params),
+        }
+    }
+}
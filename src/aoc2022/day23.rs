@@ -1,323 +1,105 @@
-use std::{collections::{HashMap, HashSet}, time::Instant};
+use crate::{answer::Answer, error::Result, params::{Overrides, ParamSet}, solution::{Parsed, Part, Solution}, utils::{Direction8, Grid, SparseGrid}};
+use std::collections::HashMap;
 
-use colored::Colorize;
+const DAY: u32 = 23;
 
 // Row, col
 type Position = (isize, isize);
 
-pub struct Elf {
-    pos: Position,
-    choice: Option<Position>,
-}
+/// Where the elves are, with nothing stored for the ground between them
+pub type Grove = SparseGrid<()>;
 
-impl Elf {
-    fn new(pos: Position) -> Elf {
-        Elf { pos, choice: None }
-    }
-    fn choose(&mut self, map: &mut HashMap<Position, bool>, directions: &Vec<fn(Position) -> [Position; 3]>) 
-        -> Option<[Position; 3]> {
-        let mut found_any = false;
-        for direction in directions {
-            for choice in (direction)(self.pos) {
-                if !map.contains_key(&choice) {
-                    map.insert(choice, false);
-                }
-                if map[&choice] { found_any = true; }
-            }
-        }
-        if !found_any {
-            self.choice = None;
-            return None;
-        }
-        let mut final_direction = None;
-        for direction in directions {
-            final_direction = Some((direction)(self.pos));
+/// The ways the elves look to move, in the order they look the first round
+static DIRECTIONS: [Direction8; 4] = [Direction8::North, Direction8::South, Direction8::West, Direction8::East];
 
-            let mut is_possible = true;
-            for choice in (direction)(self.pos) {
-                if !map.contains_key(&choice) {
-                    map.insert(choice, false);
-                }
-                if map[&choice] { is_possible = false; }
-            }
-            if is_possible {
-                self.choice = Some((direction)(self.pos)[1]);
-                break;
-            }
-        }
-        final_direction
-    }
+pub fn parse(input: &str) -> Result<Grove> {
+    let scan = Grid::parse(DAY, input, |c| matches!(c, '#' | '.').then_some(c == '#'), "'#' or '.'")?;
+    Ok(scan.iter()
+        .filter(|(_, elf)| **elf)
+        .map(|((row, col), _)| ((row as isize, col as isize), ()))
+        .collect())
 }
 
-fn north(pos: Position) -> [Position; 3] {
-    [(pos.0 - 1, pos.1 - 1), (pos.0 - 1, pos.1), (pos.0 - 1, pos.1 + 1)]
-}
-fn south(pos: Position) -> [Position; 3] {
-    [(pos.0 + 1, pos.1 - 1), (pos.0 + 1, pos.1), (pos.0 + 1, pos.1 + 1)]
-}
-fn west(pos: Position) -> [Position; 3] {
-    [(pos.0 - 1, pos.1 - 1), (pos.0 , pos.1 - 1), (pos.0 + 1, pos.1 - 1)]
-}
-fn east(pos: Position) -> [Position; 3] {
-    [(pos.0 - 1, pos.1 + 1), (pos.0, pos.1 + 1), (pos.0 + 1, pos.1 + 1)]
-}
-
-static DIRECTIONS: [fn(Position) -> [Position; 3]; 4] = [north, south, west, east];
-
-fn read_map(input: &str) -> (HashMap<Position, bool>, Vec<Elf>, isize, isize) {
-    let mut map = HashMap::new();
-    let mut elves = Vec::new();
-    let mut max_col = 0;
-    let mut row = 0;
-    for line in input.split("\n") {
-        let mut col = 0;
-        for c in line.chars() {
-            map.insert((row, col), match c {
-                '#' => true,
-                '.' => false,
-                _ => unreachable!(),
-            });
-            if c == '#' {
-                elves.push(Elf::new((row, col)));
-            }
-
-            col += 1;
-        }
-        if col > max_col { max_col = col; }
-        row += 1;
+/// Where the elf at `pos` wants to move to, if anywhere
+fn choose(elves: &Grove, pos: Position, directions: &[Direction8]) -> Option<Position> {
+    let step = |dir: Direction8| (pos.0 + dir.offset().0, pos.1 + dir.offset().1);
+    if Direction8::ALL.into_iter().all(|dir| !elves.contains(step(dir))) {
+        return None;
     }
-    (map, elves, row, max_col)
+    // An elf can go a way if there's no one that way, or diagonally either side of it
+    let is_clear = |dir: Direction8| [dir.turn_left(), dir, dir.turn_right()].into_iter().all(|dir| !elves.contains(step(dir)));
+    directions.iter().find(|&&dir| is_clear(dir)).map(|&dir| step(dir))
 }
 
-fn _print_map(map: &mut HashMap<Position, bool>, min: Position, max: Position) {
-    for row in min.0..max.0 {
-        for col in min.1..max.1 {
-            if !map.contains_key(&(row, col)) {
-                map.insert((row, col), false);
-            }
-            print!("{}", 
-                if map[&(row, col)] { "#" } else { "." }
-            ); 
+/// Moves every elf that's the only one wanting to go where it chose, saying whether any did
+fn spread(elves: &mut Grove, directions: &[Direction8]) -> bool {
+    // Each position chosen, with the elf that chose it, or None once a second elf does
+    let mut chosen = HashMap::new();
+    for pos in elves.positions() {
+        if let Some(choice) = choose(elves, pos, directions) {
+            chosen.entry(choice).and_modify(|from| *from = None).or_insert(Some(pos));
         }
-        println!();
     }
-}
 
-fn map_is_correct(map: &HashMap<Position, bool>, elves: &Vec<Elf>) -> bool {
-    for elf in elves {
-        if !map[&elf.pos] { return false; }
-    }
-    for key in map.keys() {
-        if map[key] {
-            let mut one_found = false;
-            for elf in elves { 
-                if elf.pos == *key { one_found = true; }
-            }
-            if !one_found { return false; }
+    let mut moved = false;
+    for (choice, from) in chosen {
+        if let Some(from) = from {
+            elves.remove(from);
+            elves.insert(choice, ());
+            moved = true;
         }
     }
-    true
+    moved
 }
 
-const NUM_P1_ROUNDS: usize = 10;
-
-fn shrink_areas(map: &mut HashMap<Position, bool>, mins: Position, maxes: Position) -> (Position, Position) {
-    let (minr, minc) = mins;
-    let (maxr, maxc) = maxes;
-    let mut new_minr = minr;
-    'outer: loop {
-        for col in minc..maxc {
-            if !map.contains_key(&(new_minr, col)) {
-                map.insert((new_minr, col), false);
-            }
-
-            if map[&(new_minr, col)] { break 'outer; }
-        }
-        new_minr += 1;
-    }
-
-    let mut new_maxr = maxr;
-    'outer: loop {
-        for col in minc..maxc {
-            if !map.contains_key(&(new_maxr - 1, col)) {
-                map.insert((new_maxr - 1, col), false);
-            }
-
-            if map[&(new_maxr - 1, col)] { break 'outer; }
-        }
-        new_maxr -= 1;
-    }
-
-    let mut new_minc = minc;
-    'outer: loop {
-        for row in minr..maxr {
-            if !map.contains_key(&(row, new_minc)) {
-                map.insert((row, new_minc), false);
-            }
-
-            if map[&(row, new_minc)] { break 'outer; }
-        }
-        new_minc += 1;
-    }
-
-    let mut new_maxc = maxc;
-    'outer: loop {
-        for row in minr..maxr {
-            if !map.contains_key(&(row, new_maxc - 1)) {
-                map.insert((row, new_maxc - 1), false);
-            }
-
-            if map[&(row, new_maxc - 1)] { break 'outer; }
-        }
-        new_maxc -= 1;
-    }
-
-    ((new_minr, new_minc), (new_maxr, new_maxc))
+crate::params! {
+    /// Rounds the elves spread out for in part 1
+    num_p1_rounds: usize = 10,
 }
 
-pub fn part1(input: &str) {
-    let mut directions = Vec::from(DIRECTIONS);
-    let (mut map, mut elves, mut max_row, mut max_col) = read_map(input);
-    let mut min_row = 0; let mut min_col = 0;
-        
-    //print_map(&mut map, (min_row, min_col), (max_row, max_col));
-    //println!();
-
-    for _ in 0..NUM_P1_ROUNDS {
-        assert!(map_is_correct(&map, &elves));
-
-        let mut positions_chosen = HashSet::new();
-        let mut duplicates = HashSet::new();
-        let mut all_none = true;
-
-        for elf in &mut elves {
-            let added = elf.choose(&mut map, &directions);
-            if let Some(added) = added {
-                for (row, col) in added {
-                    // Maxes go outside the actual boundary
-
-                    if row < min_row { min_row = row; }
-                    else if row >= max_row { max_row = row + 1; }  
-
-                    if col < min_col { min_col = col; }
-                    else if col >= max_col { max_col = col + 1; }  
-                }
-            }
-            if let Some(pos) = elf.choice {
-                all_none = false;
-                if !positions_chosen.insert(pos) {
-                    duplicates.insert(pos);
-                }
-            }
-        }
-        
-        if all_none { break; }
-
-        for elf in elves.iter_mut()
-            .filter(|elf| elf.choice.is_some() && !duplicates.contains(&elf.choice.unwrap())) 
-        {
-            let new_pos = elf.choice.unwrap();
-            map.insert(elf.pos, false);
-            map.insert(new_pos, true);
-            elf.pos = new_pos;
-            elf.choice = None;
-        }
-        // Cycle directions
-        let mut new_directions = directions.split_off(1);
-        new_directions.append(&mut directions);
-        directions = new_directions;
-    }
-
-    //print_map(&mut map, (min_row, min_col), (max_row, max_col));
-    //println!();
+pub fn part1(grove: &Grove) -> Result<Answer> {
+    part1_with(grove, &Params::default())
+}
 
-    let (new_mins, new_maxes) = shrink_areas(&mut map, (min_row, min_col), (max_row, max_col));
-    let mut empty = 0;
-    for row in new_mins.0..new_maxes.0 {
-        for col in new_mins.1..new_maxes.1 {
-            if !map.contains_key(&(row, col)) {
-                map.insert((row, col), false);
-            }
-            if !map[&(row, col)] { empty += 1; }
-        }
+pub fn part1_with(grove: &Grove, params: &Params) -> Result<Answer> {
+    let mut directions = DIRECTIONS;
+    let mut elves = grove.clone();
+    for _ in 0..params.num_p1_rounds {
+        spread(&mut elves, &directions);
+        directions.rotate_left(1);
     }
 
-    println!("{}", empty);
+    let Some(((top, left), (bottom, right))) = elves.bounds() else { return Ok(0.into()) };
+    let area = (bottom - top + 1) * (right - left + 1);
+    Ok((area as usize - elves.len()).into())
 }
 
-pub fn part2(input: &str) {
-    let mut directions = Vec::from(DIRECTIONS);
-    let (mut map, mut elves, mut max_row, mut max_col) = read_map(input);
-    let mut min_row = 0; let mut min_col = 0;
-        
+pub fn part2(grove: &Grove) -> Result<Answer> {
+    let mut directions = DIRECTIONS;
+    let mut elves = grove.clone();
     let mut count = 1;
-    loop {
-
-        assert!(map_is_correct(&map, &elves));
-
-        let mut positions_chosen = HashSet::new();
-        let mut duplicates = HashSet::new();
-        let mut all_none = true;
-
-        for elf in &mut elves {
-            let added = elf.choose(&mut map, &directions);
-            if let Some(added) = added {
-                for (row, col) in added {
-                    // Maxes go outside the actual boundary
-
-                    if row < min_row { min_row = row; }
-                    else if row >= max_row { max_row = row + 1; }  
+    while spread(&mut elves, &directions) {
+        count += 1;
+        directions.rotate_left(1);
+    }
+    Ok(count.into())
+}
 
-                    if col < min_col { min_col = col; }
-                    else if col >= max_col { max_col = col + 1; }  
-                }
-            }
-            if let Some(pos) = elf.choice {
-                all_none = false;
-                if !positions_chosen.insert(pos) {
-                    duplicates.insert(pos);
-                }
-            }
-        }
+pub struct Day23;
 
-        if all_none ||
-        elves.iter_mut()
-        .filter(|elf| elf.choice.is_some() && !duplicates.contains(&elf.choice.unwrap())) 
-        .count() == 0
-        {
-            println!("{}", count);  
-            break;
-        }
+impl Solution for Day23 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Grove>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<Grove>()) }
+    fn params(&self) -> Vec<(String, i64)> { Params::defaults() }
 
-        for elf in elves.iter_mut()
-            .filter(|elf| elf.choice.is_some() && !duplicates.contains(&elf.choice.unwrap())) 
-        {
-            let new_pos = elf.choice.unwrap();
-            map.insert(elf.pos, false);
-            map.insert(new_pos, true);
-            elf.pos = new_pos;
-            elf.choice = None;
+    fn solve_with(&self, part: Part, parsed: &Parsed, overrides: &Overrides) -> Result<Answer> {
+        let params = Params::with_overrides(DAY, overrides)?;
+        match part {
+            Part::One => part1_with(parsed.get::<Grove>(), &params),
+            Part::Two => part2(parsed.get::<Grove>()),
         }
-        for elf in &mut elves {
-            elf.choice = None;
-        }
-
-        count += 1;
-        let mut new_directions = directions.split_off(1);
-        new_directions.append(&mut directions);
-        directions = new_directions;
     }
-
 }
-
-pub fn day23(input: &str) {
-    println!("{}", "Day 23:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
@@ -1,99 +1,56 @@
-use std::time::Instant;
-use colored::Colorize;
-
-type Position = (usize, usize);
-
-fn up(pos: Position, max_row: usize, _max_col: usize) -> Position {
-    if pos.0 - 1 == 0 { (max_row - 1, pos.1) }
-    else { (pos.0 - 1, pos.1) }    
-} 
-fn down(pos: Position, max_row: usize, _max_col: usize) -> Position {
-    if pos.0 + 1 == max_row { (1, pos.1) }
-    else { (pos.0 + 1, pos.1) }    
-} 
-fn left(pos: Position, _max_row: usize, max_col: usize) -> Position {
-    if pos.1 - 1 == 0 { (pos.0, max_col - 1) }
-    else { (pos.0, pos.1 - 1) }    
-} 
-fn right(pos: Position, _max_row: usize, max_col: usize) -> Position {
-    if pos.1 + 1 == max_col { (pos.0, 1) }
-    else { (pos.0, pos.1 + 1) }      
-} 
+use crate::{answer::Answer, error::{AocError, Result}, solution::{Parsed, Solution}, utils::{grid::Grid, Direction}};
 
+const DAY: u32 = 24;
 
+type Position = (usize, usize);
 
-static DIRECTIONS: [fn(Position, usize, usize) -> Position; 4] = [up, down, left, right];
 static START_POS: Position = (0, 1);
 
 #[derive(Clone, Copy, Debug)]
 struct Blizzard {
-    idx: usize,
+    dir: Direction,
     pos: Position,
 }
 
 impl Blizzard {
     fn next_pos(&self, max_row: usize, max_col: usize) -> Position {
-        (DIRECTIONS[self.idx])(self.pos, max_row, max_col)
+        // Blizzards leaving the valley come back in on the other side
+        self.dir.step_wrapping(self.pos, (1, 1), (max_row - 1, max_col - 1))
     }
 }
 
-fn create_board(input: &str) -> (Vec<Blizzard>, usize, usize) {
-    let mut blizzards = Vec::new();
-    let mut col = 0;
-    let mut row = 0;
-    for line in input.split("\n") {
-        col = 0;
-        for c in line.chars() {
-            match c {
-                '^' => blizzards.push(Blizzard { idx: 0, pos: (row, col) }),
-                'v' => blizzards.push(Blizzard { idx: 1, pos: (row, col) }),
-                '<' => blizzards.push(Blizzard { idx: 2, pos: (row, col) }),
-                '>' => blizzards.push(Blizzard { idx: 3, pos: (row, col) }),
-                _ => (),
-            }
-            col += 1;
-        }
-        row += 1;
-    }
-    (blizzards, row - 1, col - 1)
+/// The valley's walls, and the blizzards where they start
+pub struct Valley {
+    walls: Grid<bool>,
+    blizzards: Vec<Blizzard>,
 }
 
-fn is_legal_pos(pos: Position, max_row: usize, max_col: usize) -> bool {
-    if pos == START_POS { true }
-    else if pos == (max_row, max_col - 1) { true }
-    else { pos.0 != 0 && pos.0 != max_row && pos.1 != 0 && pos.1 != max_col }
+pub fn parse(input: &str) -> Result<Valley> {
+    let tiles = Grid::parse(DAY, input, |c| matches!(c, '#' | '.' | '^' | 'v' | '<' | '>').then_some(c), "'#', '.' or a blizzard")?;
+    if tiles.height() < 3 || tiles.width() < 3 {
+        return Err(AocError::no_answer(DAY, "the valley has no room inside its walls"));
+    }
+    let blizzards = tiles.iter()
+        .filter_map(|(pos, &c)| {
+            let dir = Direction::from_char(c)?;
+            Some(Blizzard { dir, pos })
+        })
+        .collect();
+    Ok(Valley { walls: tiles.map(|&c| c == '#'), blizzards })
 }
 
-fn search_bfs(blizzards: Vec<Blizzard>, max_row: usize, max_col: usize) -> usize {
+fn search_bfs(blizzards: Vec<Blizzard>, walls: &Grid<bool>) -> usize {
+    let (max_row, max_col) = (walls.height() - 1, walls.width() - 1);
     let mut minute = 1;
     let mut next = blizzards.iter()
-    .map(|b| Blizzard { idx: b.idx, pos: b.next_pos(max_row, max_col) })
+    .map(|b| Blizzard { dir: b.dir, pos: b.next_pos(max_row, max_col) })
     .collect::<Vec<_>>();
     let mut possible = vec![START_POS];
     loop {
         let mut new_possibilities = possible.clone();
 
         for pos in possible {
-             // up
-            if pos.0 != 0 {
-                let new_pos = (pos.0 - 1, pos.1);
-                if is_legal_pos(new_pos, max_row, max_col) { new_possibilities.push(new_pos); }
-            }
-            // down
-            if pos.0 != max_row {
-                let new_pos = (pos.0 + 1, pos.1);
-                if is_legal_pos(new_pos, max_row, max_col) { new_possibilities.push(new_pos); }
-            }
-            // left
-            if pos.1 != 0 {
-                let new_pos = (pos.0, pos.1 - 1);
-                if is_legal_pos(new_pos, max_row, max_col) { new_possibilities.push(new_pos); }
-            }
-            // right 
-            if pos.1 != max_col {
-                let new_pos = (pos.0, pos.1 + 1);
-                if is_legal_pos(new_pos, max_row, max_col) { new_possibilities.push(new_pos); }
-            }
+            new_possibilities.extend(walls.neighbors4(pos).filter(|&pos| !walls[pos]));
         }
         new_possibilities.sort();
         new_possibilities.dedup();
@@ -121,36 +78,18 @@ fn search_bfs(blizzards: Vec<Blizzard>, max_row: usize, max_col: usize) -> usize
     }
 }
 
-fn search_bfs_pt2(blizzards: Vec<Blizzard>, max_row: usize, max_col: usize) -> usize {
+fn search_bfs_pt2(blizzards: Vec<Blizzard>, walls: &Grid<bool>) -> usize {
+    let (max_row, max_col) = (walls.height() - 1, walls.width() - 1);
     let mut minute = 1;
     let mut next = blizzards.iter()
-    .map(|b| Blizzard { idx: b.idx, pos: b.next_pos(max_row, max_col) })
+    .map(|b| Blizzard { dir: b.dir, pos: b.next_pos(max_row, max_col) })
     .collect::<Vec<_>>();
     let mut possible = vec![START_POS];
     'first: loop {
         let mut new_possibilities = possible.clone();
 
         for pos in possible {
-             // up
-            if pos.0 != 0 {
-                let new_pos = (pos.0 - 1, pos.1);
-                if is_legal_pos(new_pos, max_row, max_col) { new_possibilities.push(new_pos); }
-            }
-            // down
-            if pos.0 != max_row {
-                let new_pos = (pos.0 + 1, pos.1);
-                if is_legal_pos(new_pos, max_row, max_col) { new_possibilities.push(new_pos); }
-            }
-            // left
-            if pos.1 != 0 {
-                let new_pos = (pos.0, pos.1 - 1);
-                if is_legal_pos(new_pos, max_row, max_col) { new_possibilities.push(new_pos); }
-            }
-            // right 
-            if pos.1 != max_col {
-                let new_pos = (pos.0, pos.1 + 1);
-                if is_legal_pos(new_pos, max_row, max_col) { new_possibilities.push(new_pos); }
-            }
+            new_possibilities.extend(walls.neighbors4(pos).filter(|&pos| !walls[pos]));
         }
         new_possibilities.sort();
         new_possibilities.dedup();
@@ -186,26 +125,7 @@ fn search_bfs_pt2(blizzards: Vec<Blizzard>, max_row: usize, max_col: usize) -> u
         let mut new_possibilities = possible.clone();
 
         for pos in possible {
-             // up
-            if pos.0 != 0 {
-                let new_pos = (pos.0 - 1, pos.1);
-                if is_legal_pos(new_pos, max_row, max_col) { new_possibilities.push(new_pos); }
-            }
-            // down
-            if pos.0 != max_row {
-                let new_pos = (pos.0 + 1, pos.1);
-                if is_legal_pos(new_pos, max_row, max_col) { new_possibilities.push(new_pos); }
-            }
-            // left
-            if pos.1 != 0 {
-                let new_pos = (pos.0, pos.1 - 1);
-                if is_legal_pos(new_pos, max_row, max_col) { new_possibilities.push(new_pos); }
-            }
-            // right 
-            if pos.1 != max_col {
-                let new_pos = (pos.0, pos.1 + 1);
-                if is_legal_pos(new_pos, max_row, max_col) { new_possibilities.push(new_pos); }
-            }
+            new_possibilities.extend(walls.neighbors4(pos).filter(|&pos| !walls[pos]));
         }
         new_possibilities.sort();
         new_possibilities.dedup();
@@ -241,26 +161,7 @@ fn search_bfs_pt2(blizzards: Vec<Blizzard>, max_row: usize, max_col: usize) -> u
         let mut new_possibilities = possible.clone();
 
         for pos in possible {
-             // up
-            if pos.0 != 0 {
-                let new_pos = (pos.0 - 1, pos.1);
-                if is_legal_pos(new_pos, max_row, max_col) { new_possibilities.push(new_pos); }
-            }
-            // down
-            if pos.0 != max_row {
-                let new_pos = (pos.0 + 1, pos.1);
-                if is_legal_pos(new_pos, max_row, max_col) { new_possibilities.push(new_pos); }
-            }
-            // left
-            if pos.1 != 0 {
-                let new_pos = (pos.0, pos.1 - 1);
-                if is_legal_pos(new_pos, max_row, max_col) { new_possibilities.push(new_pos); }
-            }
-            // right 
-            if pos.1 != max_col {
-                let new_pos = (pos.0, pos.1 + 1);
-                if is_legal_pos(new_pos, max_row, max_col) { new_possibilities.push(new_pos); }
-            }
+            new_possibilities.extend(walls.neighbors4(pos).filter(|&pos| !walls[pos]));
         }
         new_possibilities.sort();
         new_possibilities.dedup();
@@ -313,13 +214,7 @@ fn _print_valley(blizzards: &Vec<Blizzard>, max_row: usize, max_col: usize) {
                     print!("{}", found_count);
                 }
                 else {
-                    print!("{}", match found.unwrap().idx {
-                        0 => "^",
-                        1 => "v",
-                        2 => "<",
-                        3 => ">",
-                        _ => unreachable!(),
-                    });
+                    print!("{}", found.unwrap().dir.arrow());
                 }
             }   
         }
@@ -327,26 +222,22 @@ fn _print_valley(blizzards: &Vec<Blizzard>, max_row: usize, max_col: usize) {
     }
 }
 
-pub fn part1(input: &str) {
-    let (blizzards, max_row, max_col) = create_board(input);
-    let min = search_bfs(blizzards.clone(), max_row, max_col);    
-    println!("{}", min);
+pub fn part1(valley: &Valley) -> Result<Answer> {
+    let min = search_bfs(valley.blizzards.clone(), &valley.walls);    
+    Ok(min.into())
 }
 
-pub fn part2(input: &str) {
-    let (blizzards, max_row, max_col) = create_board(input);
-    let min = search_bfs_pt2(blizzards.clone(), max_row, max_col);    
-    println!("{}", min);
+pub fn part2(valley: &Valley) -> Result<Answer> {
+    let min = search_bfs_pt2(valley.blizzards.clone(), &valley.walls);    
+    Ok(min.into())
 }
 
-pub fn day24(input: &str) {
-    println!("{}", "Day 24:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
+pub struct Day24;
+
+impl Solution for Day24 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Valley>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<Valley>()) }
+}
@@ -1,7 +1,9 @@
-use colored::Colorize;
+use crate::{answer::Answer, error::Result, parse::{Line, Lines}, solution::{Parsed, Solution}};
 
 // ty StackOverflow!
-use std::{char::from_digit, time::Instant};
+use std::char::from_digit;
+
+const DAY: u32 = 25;
 fn encode(mut n: u128, r: u128) -> Option<String> {
    let mut s = String::new();
    loop {
@@ -18,23 +20,21 @@ fn encode(mut n: u128, r: u128) -> Option<String> {
    Some(s)
 }
 
-fn snafu_to_decimal(snafu: &str) -> u128 {
+fn snafu_to_decimal(snafu: Line) -> Result<u128> {
     let mut val = 0;
-    let mut place = 0;
-    for c in snafu.chars().rev() {
+    for (column, c) in snafu.columns() {
         let place_val: i128 = match c {
             '2' => 2,
             '1' => 1,
             '0' => 0,
             '-' => -1,
             '=' => -2,
-            _ => unreachable!(),
+            _ => return Err(snafu.error_at(column, "a SNAFU digit")),
         };
-        val += place_val * 5_i128.pow(place);
-        place += 1; 
+        val = val * 5 + place_val;
     }
 
-    val as u128
+    Ok(val as u128)
 }
 
 fn decimal_to_snafu(dec: u128) -> String {
@@ -81,18 +81,21 @@ fn decimal_to_snafu(dec: u128) -> String {
     snafu
 }
 
-pub fn part1(input: &str) {
-    let mut num = 0;
-    for line in input.split("\n") {
-        num += snafu_to_decimal(line);
-    }
-    println!("{}", decimal_to_snafu(num));
+pub fn parse(input: &str) -> Result<Vec<u128>> {
+    Lines::new(DAY, input).map(snafu_to_decimal).collect()
+}
+
+pub fn part1(nums: &[u128]) -> Result<Answer> {
+    Ok(decimal_to_snafu(nums.iter().sum()).into())
 }
 
-pub fn day25(input: &str) {
-    println!("{}", "Day 25:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-}
\ No newline at end of file
+pub struct Day25;
+
+impl Solution for Day25 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Vec<u128>>()) }
+    fn part2(&self, _parsed: &Parsed) -> Result<Answer> { unreachable!("Day 25 has no part 2") }
+    fn has_part2(&self) -> bool { false }
+}
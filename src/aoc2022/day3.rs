@@ -1,8 +1,40 @@
-use std::time::Instant;
-use colored::Colorize;
+use crate::{answer::Answer, error::{AocError, Result}, parse::Lines, solution::{Parsed, Solution}};
 
-pub fn part1(input: &str) {
-    println!("{}", input.split("\n").into_iter().map(|s| {
+const DAY: u32 = 3;
+
+pub struct Rucksack {
+    /// The line of the input it's on, for pointing at it in errors
+    line: usize,
+    items: String,
+}
+
+/// Each rucksack's items, checked to be letters split evenly between two compartments
+pub fn parse(input: &str) -> Result<Vec<Rucksack>> {
+    let mut rucksacks = Vec::new();
+    for line in Lines::new(DAY, input) {
+        if let Some((column, _)) = line.columns().find(|(_, c)| !c.is_ascii_alphabetic()) {
+            return Err(line.error_at(column, "an item letter"));
+        }
+        if line.text.len() % 2 == 1 {
+            return Err(line.error_at(line.text.len() + 1, "an even number of items"));
+        }
+        rucksacks.push(Rucksack { line: line.number, items: line.text.to_string() });
+    }
+    Ok(rucksacks)
+}
+
+fn priority(item: u32) -> u32 {
+    if item >= 97 {
+        item - 96
+    } else {
+        item - 38
+    }
+}
+
+pub fn part1(rucksacks: &[Rucksack]) -> Result<Answer> {
+    let mut total = 0;
+    for rucksack in rucksacks {
+        let s = &rucksack.items;
         let (first, second) = s.split_at(s.len() / 2);
         let mut both = 0;
         for b in first.as_bytes() {
@@ -11,21 +43,23 @@ pub fn part1(input: &str) {
                 break;
             }
         }
-        if both >= 97 {
-            both - 96
-        } else {
-            both - 38
+        if both == 0 {
+            return Err(AocError::no_answer(DAY, format!("rucksack on line {} has no item in both compartments", rucksack.line)));
         }
-    }).sum::<u32>());
+        total += priority(both);
+    }
+    Ok(total.into())
 }
 
-pub fn part2(input: &str) {
+pub fn part2(rucksacks: &[Rucksack]) -> Result<Answer> {
     let mut total = 0;
-    let mut splits = input.split("\n").collect::<Vec<&str>>();
-    while splits.len() > 0 {
-        let first = splits[0];
-        let second = splits[1];
-        let third = splits[2];
+    if !rucksacks.len().is_multiple_of(3) {
+        return Err(AocError::no_answer(DAY, "the elves can't be split into groups of three"));
+    }
+    for group in rucksacks.chunks(3) {
+        let first = &group[0].items;
+        let second = &group[1].items;
+        let third = &group[2].items;
 
         let mut both = 0;
         for b in first.as_bytes() {
@@ -35,27 +69,20 @@ pub fn part2(input: &str) {
             }
         }
         if both == 0 {
-            panic!("{first}\n{second}\n{third}");
+            return Err(AocError::no_answer(DAY, format!("group starting on line {} has no badge in common", group[0].line)));
         }
 
-        if both >= 97 {
-            total += both - 96;
-        } else {
-            total += both - 38;
-        }
-        splits.remove(0); splits.remove(0); splits.remove(0);
+        total += priority(both);
     }
-    println!("{total}");
+    Ok(total.into())
 }
 
-pub fn day3(input: &str) {
-    println!("{}", "Day 3:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Vec<Rucksack>>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<Vec<Rucksack>>()) }
+}
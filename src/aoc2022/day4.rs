@@ -1,31 +1,36 @@
-use std::time::Instant;
-use colored::Colorize;
+use crate::{answer::Answer, error::Result, parse::Lines, solution::{Parsed, Solution}};
+
+const DAY: u32 = 4;
+
+/// Each pair's section numbers, as the first elf's start and end then the second's
+pub fn parse(input: &str) -> Result<Vec<[i32; 4]>> {
+    Lines::new(DAY, input)
+        .map(|line| line.parse(|c| {
+            let num1 = c.int()?;
+            c.tag("-")?;
+            let num2 = c.int()?;
+            c.tag(",")?;
+            let num3 = c.int()?;
+            c.tag("-")?;
+            let num4 = c.int()?;
+            Ok([num1, num2, num3, num4])
+        }))
+        .collect()
+}
 
-pub fn part1(input: &str) {
+pub fn part1(pairs: &[[i32; 4]]) -> Result<Answer> {
     let mut total = 0;
-    for line in input.split("\n") {
-
-        let num1: i32 = line.split("-").next().unwrap().parse().unwrap();
-        let num2: i32 = line.split(",").next().unwrap().split("-").skip(1).next().unwrap().parse().unwrap();
-        let num3: i32 = line.split(",").skip(1).next().unwrap().split("-").next().unwrap().parse().unwrap();
-        let num4: i32 = line.split("-").skip(2).next().unwrap().parse().unwrap();
-
+    for &[num1, num2, num3, num4] in pairs {
         if (num1 >= num3 && num2 <= num4) || (num3 >= num1 && num4 <= num2)  {
             total += 1;
         }
     }
-    println!("{total}");
+    Ok(total.into())
 }
 
-pub fn part2(input: &str) {
+pub fn part2(pairs: &[[i32; 4]]) -> Result<Answer> {
     let mut total = 0;
-    for line in input.split("\n") {
-
-        let mut num1: i32 = line.split("-").next().unwrap().parse().unwrap();
-        let mut num2: i32 = line.split(",").next().unwrap().split("-").skip(1).next().unwrap().parse().unwrap();
-        let mut num3: i32 = line.split(",").skip(1).next().unwrap().split("-").next().unwrap().parse().unwrap();
-        let mut num4: i32 = line.split("-").skip(2).next().unwrap().parse().unwrap();
-
+    for &[mut num1, mut num2, mut num3, mut num4] in pairs {
         if num1 >= num3 {
             (num1, num3) = (num3, num1);
         }
@@ -38,17 +43,15 @@ pub fn part2(input: &str) {
             total += 1;
         }
     }
-    println!("{total}");
+    Ok(total.into())
 }
 
-pub fn day4(input: &str) {
-    println!("{}", "Day 4:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
+pub struct Day4;
+
+impl Solution for Day4 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Vec<[i32; 4]>>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<Vec<[i32; 4]>>()) }
+}
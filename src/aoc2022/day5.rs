@@ -1,97 +1,128 @@
-use std::{str::Split, time::Instant};
-use colored::Colorize;
+use crate::{answer::Answer, error::{AocError, Result}, parse::{Cursor, Lines}, solution::{Parsed, Solution}};
 
-fn parse_stacks(input: &str) -> (Vec<Vec<&str>>, Split<&str>) {
-    let num_stacks = input.split("\n").next().unwrap().len() / 4 + 1;
-    let mut stacks = vec![Vec::<&str>::new(); num_stacks];
+const DAY: u32 = 5;
 
-    let mut lines = input.split("\n");
+/// One crane instruction, with stacks counted from 0
+struct Move {
+    count: usize,
+    src: usize,
+    dest: usize,
+    /// The input line it came from
+    line: usize,
+}
+
+/// The starting stacks, bottom crate first, and the moves the crane makes
+pub struct Crates {
+    stacks: Vec<Vec<char>>,
+    moves: Vec<Move>,
+}
+
+pub fn parse(input: &str) -> Result<Crates> {
+    let mut lines = Lines::new(DAY, input);
+    let num_stacks = lines.clone().expect("a row of crates")?.text.len() / 4 + 1;
+    let mut stacks = vec![Vec::<char>::new(); num_stacks];
 
     loop {
-        let line = lines.next().unwrap();
-        if line[0..1].trim().is_empty() && !line[1..2].trim().is_empty() {
+        let line = lines.expect("a row of crates or the stack numbers")?;
+        if line.text.trim_start().starts_with(|c: char| c.is_ascii_digit()) {
             break;
         }
         for idx in 0..num_stacks {
             let real_idx = idx * 4 + 1;
-            if !(&line[real_idx..real_idx + 1].trim().is_empty()) {
-                stacks[idx].push(&line[real_idx..real_idx + 1]);
+            match line.text.as_bytes().get(real_idx) {
+                None | Some(b' ') => (),
+                Some(c) if c.is_ascii_uppercase() => stacks[idx].push(*c as char),
+                Some(_) => return Err(line.error_at(real_idx + 1, "a crate letter or a space")),
             }
         }
     }
 
-    lines.next(); // Skip blank line
+    let blank = lines.expect("a blank line")?;
+    if !blank.text.trim().is_empty() {
+        return Err(blank.error_at(1, "a blank line"));
+    }
 
     for stack in &mut stacks {
         stack.reverse();
     }
-    (stacks, lines)
-}
 
-pub fn part1(input: &str) {
-    let (mut stacks, mut lines) = parse_stacks(input);
+    let stack_number = |c: &mut Cursor| {
+        let start = c.clone();
+        match c.int::<usize>()? {
+            n if (1..=num_stacks).contains(&n) => Ok(n - 1),
+            _ => Err(start.error(format!("a stack number from 1 to {num_stacks}"))),
+        }
+    };
+    let moves = lines
+        .map(|line| line.parse(|c| {
+            c.tag("move ")?;
+            let count = c.int()?;
+            c.tag(" from ")?;
+            let src = stack_number(c)?;
+            c.tag(" to ")?;
+            let dest = stack_number(c)?;
+            Ok(Move { count, src, dest, line: line.number })
+        }))
+        .collect::<Result<_>>()?;
+    Ok(Crates { stacks, moves })
+}
 
-    for struc in &mut lines {
-        let struc = struc.replace("move", "");
-        let struc = struc.replace("from", "");
-        let struc = struc.replace("to", "");
+/// Makes sure there are enough crates on the stack a move takes them from
+fn check_move(stacks: &[Vec<char>], mv: &Move) -> Result<()> {
+    if stacks[mv.src].len() < mv.count {
+        return Err(AocError::no_answer(DAY, format!("line {} moves more crates than stack {} has", mv.line, mv.src + 1)));
+    }
+    Ok(())
+}
 
-        let nums = struc.split_ascii_whitespace().collect::<Vec<&str>>();
+/// The crates on top of each stack, which all need to have something on them
+fn tops(stacks: &[Vec<char>]) -> Result<Answer> {
+    let mut tops = String::new();
+    for (idx, stack) in stacks.iter().enumerate() {
+        let top = stack.last().ok_or_else(|| AocError::no_answer(DAY, format!("stack {} ends up empty", idx + 1)))?;
+        tops.push(*top);
+    }
+    Ok(tops.into())
+}
 
-        let count = nums[0].parse().unwrap();
-        let src: usize = nums[1].parse::<usize>().unwrap() - 1;
-        let dest: usize = nums[2].parse::<usize>().unwrap() - 1;
+pub fn part1(crates: &Crates) -> Result<Answer> {
+    let mut stacks = crates.stacks.clone();
 
-        for _ in 0..count {
-            let item = stacks[src].pop().unwrap();
+    for mv in &crates.moves {
+        check_move(&stacks, mv)?;
+        for _ in 0..mv.count {
+            let item = stacks[mv.src].pop().unwrap();
 
-            stacks[dest].push(item);
+            stacks[mv.dest].push(item);
         }
     }
-    for stack in &stacks {            
-        print!("{}", stack.last().unwrap());
-    }
-    println!();
-
+    tops(&stacks)
 }
 
-pub fn part2(input: &str) {
-    let (mut stacks, mut lines) = parse_stacks(input);
-
-    for struc in &mut lines {
-        let struc = struc.replace("move", "");
-        let struc = struc.replace("from", "");
-        let struc = struc.replace("to", "");
-
-        let nums = struc.split_ascii_whitespace().collect::<Vec<&str>>();
-
-        let count = nums[0].parse().unwrap();
-        let src: usize = nums[1].parse::<usize>().unwrap() - 1;
-        let dest: usize = nums[2].parse::<usize>().unwrap() - 1;
+pub fn part2(crates: &Crates) -> Result<Answer> {
+    let mut stacks = crates.stacks.clone();
 
+    for mv in &crates.moves {
+        check_move(&stacks, mv)?;
+        let (count, src) = (mv.count, mv.src);
         for num in 0..count {
             let item = stacks[src][stacks[src].len() - (count - num)];
-            stacks[dest].push(item);
+            stacks[mv.dest].push(item);
         }
         for _ in 0..count {
             stacks[src].pop();
         }
 
     }
-    for stack in &stacks {            
-        print!("{}", stack.last().unwrap());
-    }
-    println!();
+    tops(&stacks)
 }
 
-pub fn day5(input: &str) {
-    println!("{}", "Day 5:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
+pub struct Day5;
+
+impl Solution for Day5 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Crates>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<Crates>()) }
+}
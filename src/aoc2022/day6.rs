@@ -1,12 +1,26 @@
-use std::time::Instant;
-use colored::Colorize;
+use crate::{answer::Answer, error::{AocError, Result}, parse::Lines, solution::{Parsed, Solution}};
 
-pub fn part1(input: &str) {
+const DAY: u32 = 6;
+
+/// The datastream, which has to be a single line of lowercase letters
+pub fn parse(input: &str) -> Result<String> {
+    let mut lines = Lines::new(DAY, input);
+    let line = lines.expect("the datastream")?;
+    if let Some((column, _)) = line.columns().find(|(_, c)| !c.is_ascii_lowercase()) {
+        return Err(line.error_at(column, "a lowercase letter"));
+    }
+    if let Some(extra) = lines.next() {
+        return Err(extra.error_at(1, "end of input"));
+    }
+    Ok(line.text.to_string())
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
     let span_len = 4;
     
     let mut idx = 0;
     let mut len = 1; 
-    while len < span_len + 1 {
+    while len < span_len + 1 && idx + len < input.len() {
         let s = &input[idx..idx + len];
         let newc = &input[idx + len..idx + len + 1];
 
@@ -17,22 +31,22 @@ pub fn part1(input: &str) {
         if s_idx == s.len() {
             len += 1;
             if s_idx + 1 == span_len {
-                println!("{}", idx + len);
-                return;
+                return Ok((idx + len).into());
             }
             continue;
         }
         idx += s_idx + 1;
         len -= s_idx;
     } 
+    Err(AocError::no_answer(DAY, "the datastream has no marker"))
 }
 
-pub fn part2(input: &str) {
+pub fn part2(input: &str) -> Result<Answer> {
     let span_len = 14;
     
     let mut idx = 0;
     let mut len = 1; 
-    while len < span_len + 1 {
+    while len < span_len + 1 && idx + len < input.len() {
         let s = &input[idx..idx + len];
         let newc = &input[idx + len..idx + len + 1];
 
@@ -43,24 +57,22 @@ pub fn part2(input: &str) {
         if s_idx == s.len() {
             len += 1;
             if s_idx + 1 == span_len {
-                println!("{}", idx + len);
-                return;
+                return Ok((idx + len).into());
             }
             continue;
         }
         idx += s_idx + 1;
         len -= s_idx;
     } 
+    Err(AocError::no_answer(DAY, "the datastream has no marker"))
 }
 
-pub fn day6(input: &str) {
-    println!("{}", "Day 6:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
+pub struct Day6;
+
+impl Solution for Day6 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<String>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<String>()) }
+}
@@ -1,77 +1,100 @@
-use std::{collections::HashMap, time::Instant};
-use colored::Colorize;
+use crate::{answer::Answer, error::{AocError, Result}, parse::Lines, solution::{Parsed, Solution}};
+use std::collections::HashMap;
 
-fn create_fs_layout(input: &str) -> HashMap<String, usize> {
+const DAY: u32 = 7;
+
+/// One line of the terminal output
+enum Entry<'a> {
+    Cd(&'a str),
+    Ls,
+    Dir,
+    File(usize),
+}
+
+/// The total size of every directory, keyed by its path
+pub fn parse(input: &str) -> Result<HashMap<String, usize>> {
     let mut dirs = HashMap::new();
     dirs.insert(String::from("/"), 0);
     let mut current_directory = String::from("/");
-    for line in input.split("\n") {
-        if &line[0..4] == "$ cd" {
-            let changed_to = &line[5..line.len()];
-            if changed_to == "/" {
-                current_directory = String::from("/");
-            } 
-            else if changed_to == ".." {
-                let last_dir_idx = current_directory.rfind("^").unwrap();
+    for line in Lines::new(DAY, input) {
+        let entry = line.parse(|c| {
+            if c.optional("$ cd ") {
+                Ok(Entry::Cd(c.take_rest()))
+            } else if c.optional("$ ls") {
+                Ok(Entry::Ls)
+            } else if c.optional("dir ") {
+                c.take_rest();
+                Ok(Entry::Dir)
+            } else if c.rest().starts_with(|c: char| c.is_ascii_digit()) {
+                let size = c.int()?;
+                c.tag(" ")?;
+                c.take_rest();
+                Ok(Entry::File(size))
+            } else {
+                Err(c.error("\"$ cd\", \"$ ls\", \"dir\" or a file size"))
+            }
+        })?;
+        match entry {
+            Entry::Cd("/") => current_directory = String::from("/"),
+            Entry::Cd("..") => {
+                let last_dir_idx = current_directory
+                    .rfind("^")
+                    .ok_or_else(|| AocError::no_answer(DAY, format!("line {} goes above the root directory", line.number)))?;
                 current_directory.truncate(last_dir_idx);
             }
-            else {
+            Entry::Cd(changed_to) => {
                 current_directory.push('^');
-                current_directory.push_str(&changed_to);
+                current_directory.push_str(changed_to);
             }
-        }
-        else if &line[0..1] != "$" && &line[0..3] != "dir" {
-            let mut temp_dir = current_directory.clone();
-            let size: usize = line.split_ascii_whitespace().next().unwrap().parse().unwrap();
-            while temp_dir != "/" {
-                if !dirs.contains_key(&temp_dir) {
-                    dirs.insert(temp_dir.clone(), size);
-                } else {
-                    let current_size = dirs.get_mut(&temp_dir).unwrap();
-                    *current_size += size;
+            Entry::Ls | Entry::Dir => (),
+            Entry::File(size) => {
+                let mut temp_dir = current_directory.clone();
+                while temp_dir != "/" {
+                    if !dirs.contains_key(&temp_dir) {
+                        dirs.insert(temp_dir.clone(), size);
+                    } else {
+                        let current_size = dirs.get_mut(&temp_dir).unwrap();
+                        *current_size += size;
+                    }
+                    let last_dir_idx = temp_dir.rfind("^").unwrap();
+                    temp_dir.truncate(last_dir_idx);
                 }
-                let last_dir_idx = temp_dir.rfind("^").unwrap();
-                temp_dir.truncate(last_dir_idx);
+                let current_size = dirs.get_mut("/").unwrap();
+                *current_size += size;
             }
-            let current_size = dirs.get_mut("/").unwrap();
-            *current_size += size;
         }
     }
-    dirs
+    Ok(dirs)
 }
 
-pub fn part1(input: &str) {
-    let dirs = create_fs_layout(input);
+pub fn part1(dirs: &HashMap<String, usize>) -> Result<Answer> {
     let mut total = 0;
     for val in dirs.values() {
         if *val < 100000 {
             total += *val;
         }
     }
-    println!("{total}");
+    Ok(total.into())
 }
 
-pub fn part2(input: &str) {
-    let dirs = create_fs_layout(input);
+pub fn part2(dirs: &HashMap<String, usize>) -> Result<Answer> {
     let total_space_remaining = 70000000 - dirs.get("/").unwrap();
     let mut vals = dirs.values().collect::<Vec<&usize>>();
     vals.sort();
     for val in vals {
         if val + total_space_remaining > 30000000 {
-            println!("{val}");
-            return;
+            return Ok((*val).into());
         }
     }
+    Err(AocError::no_answer(DAY, "no directory is big enough to delete"))
 }
 
-pub fn day7(input: &str) {
-    println!("{}", "Day 7:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
+pub struct Day7;
+
+impl Solution for Day7 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<HashMap<String, usize>>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<HashMap<String, usize>>()) }
+}
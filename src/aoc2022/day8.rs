@@ -1,146 +1,49 @@
-use std::time::Instant;
-use colored::Colorize;
+use crate::{answer::Answer, error::Result, solution::{Parsed, Solution}, utils::grid::{Grid, NEIGHBORS4}};
 
-pub fn part1(input: &str) {
-    let mut trees = Vec::new();
-    for line in input.split("\n") {
-        let mut new_row = Vec::new();
-        for b in line.as_bytes() {
-            new_row.push(*b - 48);
-        }
-        trees.push(new_row);
-    }
-    let mut are_visible = 0;
-    for row in 1..trees.len() - 1 {
-        for col in 1..trees.len() - 1 {
-            let mut visible = true;
-            for row_idx in 0..row {
-                if trees[row_idx][col] >= trees[row][col] {
-                    visible = false;
-                }
-            }
-            if visible {
-                are_visible += 1;
-                continue;
-            }
+const DAY: u32 = 8;
 
-            let mut visible = true;
-            for row_idx in row + 1..trees.len() {
-                if trees[row_idx][col] >= trees[row][col] {
-                    visible = false;
-                }
-            }
-            if visible {
-                are_visible += 1;
-                continue;
-            }
-
-            let mut visible = true;
-            for col_idx in 0..col {
-                if trees[row][col_idx] >= trees[row][col] {
-                    visible = false;
-                }
-            }
-            if visible {
-                are_visible += 1;
-                continue;
-            }
+/// The tree heights
+pub fn parse(input: &str) -> Result<Grid<u8>> {
+    Grid::parse(DAY, input, |c| c.to_digit(10).map(|height| height as u8), "a tree height")
+}
 
-            let mut visible = true;
-            for col_idx in col + 1..trees.len() {
-                if trees[row][col_idx] >= trees[row][col] {
-                    visible = false;
-                }
-            }
-            if visible {
-                are_visible += 1;
-                continue;
-            }
-        }
-    }
-    are_visible += (trees.len() - 2) * 4 + 4;
-    println!("{are_visible}");
+pub fn part1(trees: &Grid<u8>) -> Result<Answer> {
+    let are_visible = trees.positions()
+        .filter(|&pos| {
+            // Trees on the edge have nothing in the way, so their rays are empty
+            NEIGHBORS4.into_iter().any(|step| trees.ray(pos, step).all(|other| trees[other] < trees[pos]))
+        })
+        .count();
+    Ok(are_visible.into())
 }
 
-pub fn part2(input: &str) {
-    let mut trees = Vec::new();
-    for line in input.split("\n") {
-        let mut new_row = Vec::new();
-        for b in line.as_bytes() {
-            new_row.push(*b - 48);
-        }
-        trees.push(new_row);
-    }
-    let mut best_scenic_score = 0;
-    for row in 1..trees.len() - 1 {
-        for col in 1..trees.len() - 1 {
-            let mut scenic_score = 1;
- 
-            let mut broke = false;
-            for row_idx in (0..row).rev() {
-                if trees[row_idx][col] >= trees[row][col] {
-                    scenic_score *= row - row_idx;
-                    broke = true;
-                    break;
-                }
-            }
-            if !broke {
-                scenic_score *= row;
-            }
-            //println!("{scenic_score}");
-            let mut broke = false;
-            for row_idx in row + 1..trees.len() {
-                if trees[row_idx][col] >= trees[row][col] {
-                    scenic_score *= row_idx - row;
-                    broke = true;
-                    break;
-                }
-            }
-            if !broke {
-                scenic_score *= trees.len() - row - 1;
-            }
-            //println!("{scenic_score}");
-            let mut broke = false;
-            for col_idx in (0..col).rev() {
-                if trees[row][col_idx] >= trees[row][col] {
-                    scenic_score *= col - col_idx;
-                    broke = true;
-                    break;
-                }
-            }
-            if !broke {
-                scenic_score *= col;
-            }
-            //println!("{scenic_score}");
-            let mut broke = false;
-            for col_idx in col + 1..trees.len() {
-                if trees[row][col_idx] >= trees[row][col] {
-                    scenic_score *= col_idx - col;
-                    broke = true;
-                    break;
-                }
-            }
-            if !broke {
-                scenic_score *= trees.len() - col - 1;
-            }
-            //println!("{scenic_score}");
-            //println!("{} {} {}", row, col, trees[row][col]);
-            if scenic_score > best_scenic_score {
-                best_scenic_score = scenic_score;
-            }
-        }
-    }
-    println!("{best_scenic_score}");
+pub fn part2(trees: &Grid<u8>) -> Result<Answer> {
+    let best_scenic_score = trees.positions()
+        .map(|pos| {
+            NEIGHBORS4.into_iter()
+                .map(|step| {
+                    let mut seen = 0;
+                    for other in trees.ray(pos, step) {
+                        seen += 1;
+                        if trees[other] >= trees[pos] {
+                            break;
+                        }
+                    }
+                    seen
+                })
+                .product::<usize>()
+        })
+        .max()
+        .unwrap_or(0);
+    Ok(best_scenic_score.into())
 }
 
-pub fn day8(input: &str) {
-    println!("{}", "Day 8".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
+pub struct Day8;
+
+impl Solution for Day8 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Grid<u8>>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<Grid<u8>>()) }
+}
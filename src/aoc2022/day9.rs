@@ -1,77 +1,65 @@
-use std::{collections::HashSet, time::Instant};
-use colored::Colorize;
+use crate::{answer::Answer, error::Result, parse::Lines, solution::{Parsed, Solution}, utils::{Direction, Point2}};
+use std::collections::HashSet;
 
-fn adjust(head_pos: &mut (i32, i32), tail_pos: &mut(i32, i32)) {
-    if (head_pos.0).abs_diff(tail_pos.0) < 2 && (head_pos.1).abs_diff(tail_pos.1) < 2 { return; }
-    else  {
-        if head_pos.0 > tail_pos.0 {
-            tail_pos.0 += 1;
-        } else if head_pos.0 < tail_pos.0 {
-            tail_pos.0 -= 1;
-        }
-        if head_pos.1 > tail_pos.1 {
-            tail_pos.1 += 1;
-        } else if head_pos.1 < tail_pos.1 {
-            tail_pos.1 -= 1;
-        }
+const DAY: u32 = 9;
+
+/// Pulls the tail along behind the head once they stop touching
+fn adjust(head_pos: &Point2<isize>, tail_pos: &mut Point2<isize>) {
+    if head_pos.chebyshev(*tail_pos) > 1 {
+        *tail_pos = tail_pos.step_toward(*head_pos);
     }
 }
 
-fn sim_movement(head_pos: &mut (i32, i32), dir: &str) {
-    match dir {
-        "R" => head_pos.0 += 1,
-        "L" => head_pos.0 -= 1,
-        "U" => head_pos.1 += 1,
-        "D" => head_pos.1 -= 1,
-        _ => panic!("Had dir {}", dir),
-    }
+/// Each move of the head, as its direction and how many steps it takes
+pub fn parse(input: &str) -> Result<Vec<(Direction, u32)>> {
+    Lines::new(DAY, input)
+        .map(|line| line.parse(|c| {
+            let dir = Direction::from_char(c.one_of("RLUD")?).expect("every one of RLUD is a direction");
+            c.tag(" ")?;
+            Ok((dir, c.int()?))
+        }))
+        .collect()
 }
 
-pub fn part1(input: &str) {
-    let mut head_pos = (0,0);
-    let mut tail_pos = (0,0);
+pub fn part1(moves: &[(Direction, u32)]) -> Result<Answer> {
+    let mut head_pos = Point2::default();
+    let mut tail_pos = Point2::default();
     let mut positions = HashSet::new();
     positions.insert(tail_pos);
-    for line in input.split("\n") {
-        let dir = &line[0..1];
-        let amount = line[2..].parse().unwrap();
+    for &(dir, amount) in moves {
         for _ in 0..amount {
-            sim_movement(&mut head_pos, dir);
-            adjust(&mut head_pos, &mut tail_pos);
+            head_pos += Point2::from(dir.offset());
+            adjust(&head_pos, &mut tail_pos);
             positions.insert(tail_pos);
         }
     }
     
-    println!("{}", positions.len());
+    Ok(positions.len().into())
 }
 
-pub fn part2(input: &str) {
-    let mut knots = [(0,0); 10];
+pub fn part2(moves: &[(Direction, u32)]) -> Result<Answer> {
+    let mut knots = [Point2::default(); 10];
     let mut positions = HashSet::new();
     positions.insert(knots[9]);
-    for line in input.split("\n") {
-        let dir = &line[0..1];
-        let amount = line[2..].parse().unwrap();
+    for &(dir, amount) in moves {
         for _ in 0..amount {
-            sim_movement(&mut knots[0], dir);
+            knots[0] += Point2::from(dir.offset());
             for idx in 0..9 {
                 let (before, after) = knots.split_at_mut(idx + 1);
-                adjust(&mut before[idx], &mut after[0]);
+                adjust(&before[idx], &mut after[0]);
             }
             positions.insert(knots[9]);
         }
     }
-    println!("{}", positions.len());
+    Ok(positions.len().into())
 }
 
-pub fn day9(input: &str) {
-    println!("{}", "Day 9:".green());
-    let now = Instant::now();
-    part1(input);
-    let after_p1 = Instant::now();
-    let now_p1 = Instant::now();
-    part2(input);
-    let after_p2 = Instant::now();
-    println!("Part 1 in {}", format!("{:?}", after_p1.duration_since(now)).green());
-    println!("Part 2 in {}", format!("{:?}", after_p2.duration_since(now_p1)).green());
-}
\ No newline at end of file
+pub struct Day9;
+
+impl Solution for Day9 {
+    fn year(&self) -> u32 { 2022 }
+    fn day(&self) -> u32 { DAY }
+    fn parse(&self, input: &str) -> Result<Parsed> { parse(input).map(Parsed::new) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { part1(parsed.get::<Vec<(Direction, u32)>>()) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { part2(parsed.get::<Vec<(Direction, u32)>>()) }
+}
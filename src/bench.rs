@@ -0,0 +1,67 @@
+use std::time::Duration;
+
+use crate::{answer::Answer, error::Result, runner, solution::{Parsed, Part, Solution}};
+
+/// Summary statistics over repeated timings of one part
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarises a set of timings, or `None` if there aren't any
+    pub fn new(timings: &[Duration]) -> Option<Stats> {
+        if timings.is_empty() {
+            return None;
+        }
+        let mut sorted = timings.to_vec();
+        sorted.sort();
+        let len = sorted.len();
+
+        let median = if len.is_multiple_of(2) {
+            (sorted[len / 2 - 1] + sorted[len / 2]) / 2
+        } else {
+            sorted[len / 2]
+        };
+        // Nearest-rank percentile
+        let p95 = sorted[(len * 95).div_ceil(100) - 1];
+        let mean = sorted.iter().sum::<Duration>() / len as u32;
+
+        Some(Stats { iterations: len, min: sorted[0], median, mean, p95, max: sorted[len - 1] })
+    }
+}
+
+/// Times one part over `iterations` runs (at least one), after `warmup` untimed ones
+///
+/// Every run solves from the same `parsed` input, so parsing isn't part of the timings. Stops
+/// at the first error, since there's nothing worth timing after that.
+pub fn bench(solution: &dyn Solution, part: Part, parsed: &Parsed, warmup: usize, iterations: usize) -> Result<(Answer, Stats)> {
+    measure(warmup, iterations, || {
+        let result = runner::time(solution, part, parsed);
+        (result.answer, result.elapsed)
+    })
+}
+
+/// Times parsing the input the same way [`bench`] times a part, keeping what was parsed last
+pub fn bench_parse(solution: &dyn Solution, input: &str, warmup: usize, iterations: usize) -> Result<(Parsed, Stats)> {
+    measure(warmup, iterations, || runner::parse(solution, input))
+}
+
+fn measure<T>(warmup: usize, iterations: usize, mut run: impl FnMut() -> (Result<T>, Duration)) -> Result<(T, Stats)> {
+    for _ in 0..warmup {
+        run().0?;
+    }
+    let mut last = None;
+    let mut timings = Vec::with_capacity(iterations);
+    for _ in 0..iterations.max(1) {
+        let (result, elapsed) = run();
+        timings.push(elapsed);
+        last = Some(result?);
+    }
+    Ok((last.unwrap(), Stats::new(&timings).unwrap()))
+}
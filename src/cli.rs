@@ -0,0 +1,875 @@
+use std::{collections::{BTreeMap, BTreeSet}, fmt::Display, io::{self, Read}, ops::RangeInclusive, path::{Path, PathBuf}, process, str::FromStr, time::{Duration, Instant}};
+
+use clap::{error::ErrorKind, Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use colored::Colorize;
+use rayon::prelude::*;
+
+use crate::{alloc, answers::{AnswerFile, Verdict}, bench::{self, Stats}, dashboard, error::AocError, history::{self, Context, Entry}, input::Inputs, isolate::{self, Limits}, params::{Overrides, Tuned}, report, runner::{self, PartResult}, scaffold, watch, solution::{Part, Solution}, watchdog::Timeouts};
+
+#[derive(Parser, Debug)]
+#[command(name = "aoc", about = "Advent of Code solutions", version)]
+pub struct Cli {
+    /// Runs every registered day when no command is given
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// The directory holding puzzle inputs, as `<year>/day<N>.txt`
+    #[arg(long, global = true, env = "AOC_INPUTS", default_value = "inputs")]
+    pub inputs: PathBuf,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Run one or more days
+    Run(RunArgs),
+    /// Check answers against the ones stored in `answers/<year>.toml`
+    Verify(VerifyArgs),
+    /// Benchmark days with warm-up runs and repeated timings
+    Bench(BenchArgs),
+    /// Compare the latest benchmarks with an earlier run from the history file
+    Compare(CompareArgs),
+    /// Re-run a day whenever its source, input or example changes
+    Watch(WatchArgs),
+    /// Browse every day's answers and timings, running them from an interactive dashboard
+    Dashboard(DashboardArgs),
+    /// Start a new day from a template, with an empty input and example fixture
+    New(NewArgs),
+    /// Solve one day for a parent process running with --isolate
+    #[command(hide = true)]
+    Child(ChildArgs),
+}
+
+/// Which days to run
+#[derive(Args, Debug, Default)]
+pub struct Selection {
+    /// The year to run, e.g. 2022
+    #[arg(required_unless_present = "all")]
+    pub year: Option<u32>,
+
+    /// A day or an inclusive range of days, e.g. 16 or 10-15 (every day when left out)
+    pub days: Option<DayRange>,
+
+    /// Run every registered day of every year
+    #[arg(long, conflicts_with_all = ["year", "days"])]
+    pub all: bool,
+}
+
+#[derive(Args, Debug, Default)]
+pub struct RunArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    /// Only run this part
+    #[arg(long, short)]
+    pub part: Option<Part>,
+
+    #[command(flatten)]
+    pub input: InputArgs,
+
+    #[command(flatten)]
+    pub timeout: TimeoutArgs,
+
+    #[command(flatten)]
+    pub isolate: IsolateArgs,
+
+    #[command(flatten)]
+    pub params: ParamArgs,
+
+    /// Solve days concurrently on a thread pool, still printing them in order
+    #[arg(long)]
+    pub parallel: bool,
+
+    /// Count each part's heap allocations, reporting them next to its time
+    #[arg(long)]
+    pub alloc_stats: bool,
+
+    /// How to write the results
+    #[arg(long, value_enum, default_value_t)]
+    pub format: Format,
+}
+
+/// Output formats for `run`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Colored answers and timings for reading in a terminal
+    #[default]
+    Text,
+    /// A JSON array with one record per part
+    Json,
+    /// CSV with a header row and one record per part
+    Csv,
+}
+
+#[derive(Args, Debug, Default)]
+pub struct VerifyArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    #[command(flatten)]
+    pub input: InputArgs,
+
+    #[command(flatten)]
+    pub timeout: TimeoutArgs,
+
+    #[command(flatten)]
+    pub isolate: IsolateArgs,
+
+    #[command(flatten)]
+    pub params: ParamArgs,
+
+    /// Store answers for parts that don't have one yet
+    #[arg(long)]
+    pub record: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    pub selection: Selection,
+
+    #[command(flatten)]
+    pub input: InputArgs,
+
+    /// Only benchmark this part
+    #[arg(long, short)]
+    pub part: Option<Part>,
+
+    #[command(flatten)]
+    pub params: ParamArgs,
+
+    /// Untimed runs before measuring, to warm caches and the allocator
+    #[arg(long, default_value_t = 3)]
+    pub warmup: usize,
+
+    /// Timed runs per part
+    #[arg(long, short = 'n', default_value_t = 10)]
+    pub iterations: usize,
+
+    /// The file results are added to, tagged with the commit, compiler and machine
+    #[arg(long, value_name = "PATH", default_value = history::DEFAULT_PATH)]
+    pub history: PathBuf,
+
+    /// Don't add the results to the history file
+    #[arg(long, conflicts_with = "history")]
+    pub no_history: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct CompareArgs {
+    /// The run to compare against: the latest one on a commit starting with COMMIT, or the
+    /// one before the current run when left out
+    #[arg(long, value_name = "COMMIT")]
+    pub baseline: Option<String>,
+
+    /// The run to compare: the latest one on a commit starting with COMMIT, or the latest run
+    /// when left out
+    #[arg(long, value_name = "COMMIT")]
+    pub current: Option<String>,
+
+    /// How many percent slower a part can get before it counts as a regression
+    #[arg(long, value_name = "PERCENT", value_parser = parse_percent, default_value = "10%")]
+    pub threshold: f64,
+
+    /// The history file `bench` adds its results to
+    #[arg(long, value_name = "PATH", default_value = history::DEFAULT_PATH)]
+    pub history: PathBuf,
+}
+
+#[derive(Args, Debug)]
+pub struct WatchArgs {
+    pub year: u32,
+
+    #[arg(value_parser = parse_day)]
+    pub day: u32,
+
+    #[command(flatten)]
+    pub timeout: TimeoutArgs,
+
+    #[command(flatten)]
+    pub params: ParamArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct DashboardArgs {
+    #[command(flatten)]
+    pub timeout: TimeoutArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct NewArgs {
+    /// The year, which gets its own module if it doesn't have one yet
+    pub year: u32,
+
+    #[arg(value_parser = parse_day)]
+    pub day: u32,
+}
+
+/// The day a child process solves, with its input on stdin
+#[derive(Args, Debug)]
+pub struct ChildArgs {
+    pub year: u32,
+
+    #[arg(value_parser = parse_day)]
+    pub day: u32,
+
+    #[arg(long)]
+    pub part: Option<Part>,
+
+    #[arg(long, value_parser = parse_duration)]
+    pub timeout: Option<Duration>,
+
+    #[arg(long)]
+    pub alloc_stats: bool,
+
+    #[arg(long = "param", value_name = "NAME=VALUE")]
+    pub params: Vec<ParamOverride>,
+}
+
+/// Running each day in a child process with resource limits
+#[derive(Args, Debug, Default)]
+pub struct IsolateArgs {
+    /// Solve each day in its own child process, so a runaway day can't take the rest down
+    #[arg(long)]
+    pub isolate: bool,
+
+    /// Limit each day's address space to SIZE, e.g. 512M or 4G
+    #[arg(long, value_name = "SIZE", value_parser = parse_size, requires = "isolate")]
+    pub memory_limit: Option<u64>,
+
+    /// Limit each day to SECONDS of CPU time
+    #[arg(long, value_name = "SECONDS", requires = "isolate")]
+    pub cpu_limit: Option<u64>,
+}
+
+/// Per-day input overrides
+#[derive(Args, Debug, Default)]
+pub struct InputArgs {
+    /// Read a day's input from PATH instead, or from stdin when PATH is `-`.
+    /// Written `DAY=PATH`, or just `PATH` when running a single day
+    #[arg(long, short, value_name = "[DAY=]PATH")]
+    pub input: Vec<InputOverride>,
+}
+
+/// Per-day puzzle parameter overrides
+#[derive(Args, Debug, Default)]
+pub struct ParamArgs {
+    /// Change one of a day's puzzle parameters from its default, e.g. COLUMN_AT=10.
+    /// Written `DAY:NAME=VALUE`, or just `NAME=VALUE` for every day being run that has it
+    #[arg(long = "param", value_name = "[DAY:]NAME=VALUE")]
+    pub params: Vec<ParamOverride>,
+}
+
+/// A parameter value, for the day given or for every day that has the parameter
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParamOverride {
+    pub day: Option<u32>,
+    /// In uppercase, the way parameters are listed
+    pub name: String,
+    pub value: i64,
+}
+
+impl FromStr for ParamOverride {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (param, value) = s.split_once('=').ok_or_else(|| format!("expected NAME=VALUE, not `{s}`"))?;
+        let (day, name) = match param.split_once(':') {
+            Some((day, name)) => (Some(parse_day(day)?), name),
+            None => (None, param),
+        };
+        if name.is_empty() {
+            return Err(format!("`{s}` doesn't name a parameter"));
+        }
+        let value = value.parse().map_err(|_| format!("`{value}` isn't a whole number"))?;
+        Ok(ParamOverride { day, name: name.to_uppercase(), value })
+    }
+}
+
+/// A path to read one day's input from, written `DAY=PATH` or just `PATH`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputOverride {
+    pub day: Option<u32>,
+    pub path: PathBuf,
+}
+
+impl FromStr for InputOverride {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some((day, path)) if day.chars().all(|c| c.is_ascii_digit()) => {
+                Ok(InputOverride { day: Some(parse_day(day)?), path: PathBuf::from(path) })
+            }
+            _ => Ok(InputOverride { day: None, path: PathBuf::from(s) }),
+        }
+    }
+}
+
+/// Per-part time limits
+#[derive(Args, Debug, Default)]
+pub struct TimeoutArgs {
+    /// Give up on a part after LIMIT, e.g. 30s, 500ms or 2m, reporting it as TIMEOUT.
+    /// Written `DAY=LIMIT` to override the limit for one day
+    #[arg(long, short, value_name = "[DAY=]LIMIT")]
+    pub timeout: Vec<TimeoutOverride>,
+}
+
+/// A time limit for every day, or for one day when written `DAY=LIMIT`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeoutOverride {
+    pub day: Option<u32>,
+    pub limit: Duration,
+}
+
+impl FromStr for TimeoutOverride {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once('=') {
+            Some((day, limit)) => Ok(TimeoutOverride { day: Some(parse_day(day)?), limit: parse_duration(limit)? }),
+            None => Ok(TimeoutOverride { day: None, limit: parse_duration(s)? }),
+        }
+    }
+}
+
+/// Reads a duration written as a number with a unit of `ms`, `s` or `m`, or just a number of seconds
+fn parse_duration(s: &str) -> Result<Duration, String> {
+    let split = s.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number = number.parse::<f64>().map_err(|_| format!("{s:?} isn't a duration"))?;
+    let seconds = match unit {
+        "ms" => number / 1000.0,
+        "" | "s" => number,
+        "m" => number * 60.0,
+        _ => return Err(format!("{unit:?} isn't a unit of time, use ms, s or m")),
+    };
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+/// A percentage, with or without the `%`
+fn parse_percent(s: &str) -> Result<f64, String> {
+    let percent = s.strip_suffix('%').unwrap_or(s).parse::<f64>().map_err(|_| format!("`{s}` isn't a percentage"))?;
+    if percent.is_finite() && percent >= 0.0 {
+        Ok(percent)
+    } else {
+        Err(format!("`{s}` isn't a percentage"))
+    }
+}
+
+/// Reads a size in bytes, with an optional `K`, `M` or `G` suffix for powers of 1024
+fn parse_size(s: &str) -> Result<u64, String> {
+    let shift = match s.chars().last() {
+        Some('K') => 10,
+        Some('M') => 20,
+        Some('G') => 30,
+        _ => 0,
+    };
+    let number = if shift == 0 { s } else { &s[..s.len() - 1] };
+    number
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(1 << shift))
+        .ok_or_else(|| format!("{s:?} isn't a size, like 512M or 4G"))
+}
+
+/// An inclusive range of days, written as `16` or `10-15`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DayRange {
+    pub first: u32,
+    pub last: u32,
+}
+
+impl DayRange {
+    pub fn contains(&self, day: u32) -> bool {
+        self.range().contains(&day)
+    }
+
+    pub fn range(&self) -> RangeInclusive<u32> {
+        self.first..=self.last
+    }
+}
+
+fn parse_day(s: &str) -> Result<u32, String> {
+    let day = s.trim().parse::<u32>().map_err(|_| format!("{s:?} isn't a day"))?;
+    if !(1..=25).contains(&day) {
+        return Err(format!("day {day} isn't between 1 and 25"));
+    }
+    Ok(day)
+}
+
+impl FromStr for DayRange {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (first, last) = match s.split_once('-') {
+            Some((first, last)) => (parse_day(first)?, parse_day(last)?),
+            None => {
+                let day = parse_day(s)?;
+                (day, day)
+            }
+        };
+        if first > last {
+            return Err(format!("range {first}-{last} is backwards"));
+        }
+        Ok(DayRange { first, last })
+    }
+}
+
+impl Cli {
+    pub fn run(self) {
+        match self.command {
+            Some(Command::Run(args)) => run(&self.inputs, args),
+            Some(Command::Verify(args)) => verify(&self.inputs, args),
+            Some(Command::Bench(args)) => bench(&self.inputs, args),
+            Some(Command::Compare(args)) => compare(args),
+            Some(Command::Watch(args)) => watch(&self.inputs, args),
+            Some(Command::Dashboard(args)) => open_dashboard(&self.inputs, args),
+            Some(Command::New(args)) => new(&self.inputs, args),
+            Some(Command::Child(args)) => child(args),
+            None => run(&self.inputs, RunArgs { selection: Selection { all: true, ..Default::default() }, ..Default::default() }),
+        }
+    }
+}
+
+impl InputArgs {
+    /// Where to read the selected days' inputs, exiting with a usage error if a bare path is
+    /// given for more than one day
+    fn inputs(&self, dir: &Path, selected: &[&dyn Solution]) -> Inputs {
+        let mut inputs = Inputs::new(dir);
+        for input in &self.input {
+            match input.day {
+                Some(day) => inputs = inputs.with_override(day, &input.path),
+                None if selected.len() == 1 => inputs = inputs.with_override(selected[0].day(), &input.path),
+                None => Cli::command()
+                    .error(ErrorKind::ArgumentConflict, "--input needs a DAY= prefix when running more than one day")
+                    .exit(),
+            }
+        }
+        inputs
+    }
+}
+
+impl TimeoutArgs {
+    /// The limits given, where a bare limit sets the default for every day
+    fn timeouts(&self) -> Timeouts {
+        let mut timeouts = Timeouts::new(self.timeout.iter().rev().find(|t| t.day.is_none()).map(|t| t.limit));
+        for timeout in &self.timeout {
+            if let Some(day) = timeout.day {
+                timeouts = timeouts.with_override(day, timeout.limit);
+            }
+        }
+        timeouts
+    }
+}
+
+impl ParamArgs {
+    /// The selected days with their parameters overridden, exiting with a usage error if a
+    /// parameter isn't one any of them has
+    fn apply(&self, selected: Vec<&'static dyn Solution>) -> Vec<&'static dyn Solution> {
+        for param in &self.params {
+            let has_it = |s: &&dyn Solution| {
+                param.day.is_none_or(|day| day == s.day()) && s.params().iter().any(|(name, _)| *name == param.name)
+            };
+            if !selected.iter().any(has_it) {
+                let which = param.day.map_or(String::from("none of the days being run have"), |day| format!("day {day} doesn't have"));
+                Cli::command().error(ErrorKind::InvalidValue, format!("{which} a parameter called {}", param.name)).exit();
+            }
+        }
+        selected
+            .into_iter()
+            .map(|solution| {
+                let overrides = self.params.iter()
+                    .filter(|param| param.day.is_none_or(|day| day == solution.day()))
+                    .filter(|param| solution.params().iter().any(|(name, _)| *name == param.name))
+                    .map(|param| (param.name.clone(), param.value))
+                    .collect::<Overrides>();
+                Tuned::leak(solution, overrides)
+            })
+            .collect()
+    }
+}
+
+impl IsolateArgs {
+    /// The limits to run each day's process with, or `None` to solve days in this process
+    fn limits(&self) -> Option<Limits> {
+        self.isolate.then_some(Limits { memory: self.memory_limit, cpu: self.cpu_limit })
+    }
+}
+
+/// Solves a day here, or in a child process when `limits` are given
+fn solve(solution: &'static dyn Solution, input: &str, part: Option<Part>, timeout: Option<Duration>,
+    limits: Option<Limits>) -> Vec<PartResult> {
+    match limits {
+        Some(limits) => isolate::solve(solution, input, part, timeout, limits),
+        None => runner::solve(solution, input, part, timeout),
+    }
+}
+
+/// Reads a day's input, reporting why it couldn't be read so the other days can carry on
+fn read_input(inputs: &Inputs, solution: &dyn Solution) -> Option<String> {
+    match inputs.read(solution.year(), solution.day()) {
+        Ok(input) => Some(input),
+        Err(e) => {
+            eprintln!("{} {e}", "error:".red());
+            None
+        }
+    }
+}
+
+/// Exits with a failure once everything else has run if any inputs were missing
+fn exit_if_unread(unread: usize) {
+    if unread > 0 {
+        eprintln!("{}", format!("{unread} day{} couldn't be run", if unread == 1 { "" } else { "s" }).red());
+        process::exit(1);
+    }
+}
+
+/// The exit code when parts ran out of time but nothing else went wrong, the same one
+/// `timeout(1)` uses
+const TIMEOUT_EXIT_CODE: i32 = 124;
+
+fn is_timeout(result: &PartResult) -> bool {
+    matches!(result.answer, Err(AocError::Timeout { .. }))
+}
+
+fn report_timeouts(timeouts: usize) {
+    if timeouts > 0 {
+        println!("{}", format!("{timeouts} part{} timed out", if timeouts == 1 { "" } else { "s" }).magenta());
+    }
+}
+
+impl Selection {
+    /// Looks up the solutions picked out, exiting with a usage error if there are none
+    fn solutions(&self) -> Vec<&'static dyn Solution> {
+        let registry = crate::registry();
+        let selected = registry
+            .iter()
+            .filter(|s| self.all || self.year == Some(s.year()))
+            .filter(|s| self.days.as_ref().is_none_or(|days| days.contains(s.day())))
+            .collect::<Vec<_>>();
+
+        if selected.is_empty() {
+            Cli::command()
+                .error(ErrorKind::InvalidValue, "no registered days match that selection")
+                .exit();
+        }
+        selected
+    }
+}
+
+fn run(dir: &Path, args: RunArgs) {
+    let selected = args.params.apply(args.selection.solutions());
+    let inputs = args.input.inputs(dir, &selected);
+    let timeouts = args.timeout.timeouts();
+    let limits = args.isolate.limits();
+    let text = args.format == Format::Text;
+    if args.alloc_stats {
+        alloc::enable();
+    }
+    let start = Instant::now();
+    let mut days = Vec::new();
+    if args.parallel {
+        let available = selected
+            .iter()
+            .filter_map(|s| Some((*s, read_input(&inputs, *s)?)))
+            .collect::<Vec<_>>();
+        // An indexed collect keeps the results in day order however they finish
+        days = available
+            .par_iter()
+            .map(|(solution, input)| (*solution, report::input_hash(input), solve(*solution, input, args.part, timeouts.limit(solution.day()), limits)))
+            .collect::<Vec<_>>();
+        if text {
+            for (solution, _, results) in &days {
+                runner::print(*solution, results);
+            }
+        }
+    } else {
+        for solution in &selected {
+            let Some(input) = read_input(&inputs, *solution) else { continue };
+            let results = solve(*solution, &input, args.part, timeouts.limit(solution.day()), limits);
+            if text {
+                runner::print(*solution, &results);
+            }
+            days.push((*solution, report::input_hash(&input), results));
+        }
+    }
+    let unread = selected.len() - days.len();
+    let results = days.iter().flat_map(|(_, _, results)| results);
+    let failed = results.clone().any(|result| result.answer.is_err() && !is_timeout(result));
+    let timed_out = results.filter(|result| is_timeout(result)).count();
+
+    if text {
+        println!("Total time: {}", format!("{:?}", Instant::now().duration_since(start)).green());
+        report_timeouts(timed_out);
+    } else {
+        write_records(&selected, &days, args.format);
+    }
+    exit_if_unread(unread);
+    if failed {
+        process::exit(1);
+    }
+    if timed_out > 0 {
+        process::exit(TIMEOUT_EXIT_CODE);
+    }
+}
+
+/// Writes the results as JSON or CSV records, comparing them with the stored answers
+fn write_records(selected: &[&dyn Solution], days: &[(&dyn Solution, String, Vec<PartResult>)], format: Format) {
+    let files = load_answer_files(selected);
+    let records = days
+        .iter()
+        .flat_map(|(solution, hash, results)| {
+            let file = &files[&solution.year()];
+            results.iter().map(move |result| {
+                report::Record::new(*solution, result, hash, file.get(solution.day(), result.part))
+            })
+        })
+        .collect::<Vec<_>>();
+    let written = match format {
+        Format::Json => report::write_json(&records, io::stdout().lock()),
+        Format::Csv => report::write_csv(&records, io::stdout().lock()),
+        Format::Text => unreachable!(),
+    };
+    if let Err(e) = written {
+        eprintln!("{} {e}", "error:".red());
+        process::exit(2);
+    }
+}
+
+fn bench(dir: &Path, args: BenchArgs) {
+    let selected = args.params.apply(args.selection.solutions());
+    let inputs = args.input.inputs(dir, &selected);
+    let context = Context::current();
+    let mut entries = Vec::new();
+    let mut unread = 0;
+    let mut failures = 0;
+    println!("{:<6} {:<4} {:<5} {:>6} {:>12} {:>12} {:>12} {:>12} {:>12}",
+        "Year", "Day", "Part", "Iters", "Min", "Median", "Mean", "P95", "Max");
+    for solution in selected {
+        let Some(input) = read_input(&inputs, solution) else {
+            unread += 1;
+            continue;
+        };
+        let print_stats = |step: &dyn Display, stats: &Stats| {
+            println!("{:<6} {:<4} {:<5} {:>6} {:>12} {:>12} {:>12} {:>12} {:>12}",
+                solution.year(), solution.day(), step, stats.iterations,
+                format!("{:.2?}", stats.min), format!("{:.2?}", stats.median), format!("{:.2?}", stats.mean),
+                format!("{:.2?}", stats.p95), format!("{:.2?}", stats.max));
+        };
+        let parsed = match bench::bench_parse(solution, &input, args.warmup, args.iterations) {
+            Ok((parsed, stats)) => {
+                print_stats(&"parse", &stats);
+                parsed
+            }
+            Err(e) => {
+                println!("{:<6} {:<4} {:<5} {} {e}", solution.year(), solution.day(), "parse", "error:".red());
+                failures += 1;
+                continue;
+            }
+        };
+        for part in runner::selected_parts(solution, args.part) {
+            let stats = match bench::bench(solution, part, &parsed, args.warmup, args.iterations) {
+                Ok((_, stats)) => stats,
+                Err(e) => {
+                    println!("{:<6} {:<4} {:<5} {} {e}", solution.year(), solution.day(), part, "error:".red());
+                    failures += 1;
+                    continue;
+                }
+            };
+            print_stats(&part, &stats);
+            entries.push(Entry::new(&context, solution, part, &stats));
+        }
+    }
+    if !args.no_history && !entries.is_empty() {
+        match history::append(&args.history, &entries) {
+            Ok(()) => println!("Added to {} as {}", args.history.display(), context.describe()),
+            Err(e) => {
+                eprintln!("{} couldn't add to {}: {e}", "error:".red(), args.history.display());
+                process::exit(2);
+            }
+        }
+    }
+    exit_if_unread(unread);
+    if failures > 0 {
+        process::exit(1);
+    }
+}
+
+fn compare(args: CompareArgs) {
+    let fail = |msg: String| -> ! {
+        eprintln!("{} {msg}", "error:".red());
+        process::exit(2);
+    };
+    let entries = history::load(&args.history).unwrap_or_else(|e| fail(e.to_string()));
+    let Some(current) = history::find_run(&entries, args.current.as_deref(), None) else {
+        fail(format!("{} has no run to compare", args.history.display()));
+    };
+    // Without a baseline commit, compare with whatever ran last before the current run
+    let before = if args.baseline.is_none() { Some(current.run) } else { None };
+    let Some(baseline) = history::find_run(&entries, args.baseline.as_deref(), before) else {
+        fail(format!("{} has no run to compare with", args.history.display()));
+    };
+
+    println!("Baseline: {}", baseline.describe());
+    println!("Current:  {}", current.describe());
+    if baseline.machine != current.machine {
+        println!("{}", "The runs were on different machines, so the times may not be comparable".yellow());
+    }
+    let changes = history::compare(&entries, baseline.run, current.run);
+    println!("{:<6} {:<4} {:<5} {:>12} {:>12} {:>9}", "Year", "Day", "Part", "Baseline", "Current", "Change");
+    let mut regressions = 0;
+    for change in &changes {
+        let percent = format!("{:+.1}%", change.percent());
+        let percent = if change.is_regression(args.threshold) {
+            regressions += 1;
+            format!("{:>9} {}", percent.red(), "REGRESSION".red())
+        } else if change.percent() < -args.threshold {
+            format!("{:>9}", percent.green())
+        } else {
+            format!("{percent:>9}")
+        };
+        println!("{:<6} {:<4} {:<5} {:>12} {:>12} {percent}", change.year, change.day, change.part,
+            format!("{:.2?}", change.baseline), format!("{:.2?}", change.current));
+    }
+    if changes.is_empty() {
+        println!("The runs have no parts in common, benchmarked with the same params");
+    }
+    if regressions > 0 {
+        println!("{}", format!("{regressions} part{} slowed down by more than {}%", if regressions == 1 { "" } else { "s" }, args.threshold).red());
+        process::exit(1);
+    }
+}
+
+/// Loads the stored answers for every year in `selected`, exiting if one can't be read
+fn load_answer_files(selected: &[&dyn Solution]) -> BTreeMap<u32, AnswerFile> {
+    let mut files = BTreeMap::new();
+    for year in selected.iter().map(|s| s.year()).collect::<BTreeSet<_>>() {
+        match AnswerFile::load(year) {
+            Ok(file) => { files.insert(year, file); },
+            Err(e) => {
+                eprintln!("{} {e}", "error:".red());
+                process::exit(2);
+            }
+        }
+    }
+    files
+}
+
+fn verify(dir: &Path, args: VerifyArgs) {
+    let selected = args.params.apply(args.selection.solutions());
+    let inputs = args.input.inputs(dir, &selected);
+    let timeouts = args.timeout.timeouts();
+    let limits = args.isolate.limits();
+    let mut unread = 0;
+    let mut files = load_answer_files(&selected);
+
+    println!("{:<6} {:<4} {:<5} {:<8} {:<24} {:<24} Time", "Year", "Day", "Part", "Status", "Expected", "Actual");
+    let mut mismatches = 0;
+    let mut failures = 0;
+    let mut timed_out = 0;
+    let mut recorded = 0;
+    for solution in selected {
+        let Some(input) = read_input(&inputs, solution) else {
+            unread += 1;
+            continue;
+        };
+        let file = files.get_mut(&solution.year()).unwrap();
+        for result in solve(solution, &input, None, timeouts.limit(solution.day()), limits) {
+            let expected = file.get(solution.day(), result.part).cloned();
+            let verdict = Verdict::check(expected.as_ref(), &result.answer);
+            println!("{:<6} {:<4} {:<5} {} {:<24} {:<24} {:?}",
+                solution.year(), solution.day(), result.part, verdict.colored(),
+                expected.map_or(String::from("-"), |e| e.one_line()),
+                result.answer.as_ref().map_or(String::from("-"), |a| a.one_line()), result.elapsed);
+
+            match (verdict, result.answer) {
+                (Verdict::Pass, _) => (),
+                (Verdict::Mismatch, _) => mismatches += 1,
+                (Verdict::Timeout, _) => timed_out += 1,
+                (Verdict::Missing, Ok(answer)) => if args.record {
+                    file.insert(solution.day(), result.part, answer);
+                    recorded += 1;
+                },
+                (_, Err(e)) => {
+                    println!("       {} {e}", "error:".red());
+                    failures += 1;
+                }
+                (Verdict::Failed | Verdict::OverLimit, Ok(_)) => unreachable!(),
+            }
+        }
+    }
+
+    if recorded > 0 {
+        for file in files.values() {
+            if let Err(e) = file.save() {
+                eprintln!("{} couldn't save {}: {e}", "error:".red(), AnswerFile::path(file.year).display());
+                process::exit(2);
+            }
+        }
+        println!("Recorded {recorded} new answer{}", if recorded == 1 { "" } else { "s" });
+    }
+    if mismatches > 0 {
+        println!("{}", format!("{mismatches} answer{} didn't match", if mismatches == 1 { "" } else { "s" }).red());
+    }
+    if failures > 0 {
+        println!("{}", format!("{failures} part{} failed", if failures == 1 { "" } else { "s" }).red());
+    }
+    report_timeouts(timed_out);
+    exit_if_unread(unread);
+    if mismatches + failures > 0 {
+        process::exit(1);
+    }
+    if timed_out > 0 {
+        process::exit(TIMEOUT_EXIT_CODE);
+    }
+}
+
+fn watch(dir: &Path, args: WatchArgs) {
+    let Some(solution) = crate::registry().get(args.year, args.day) else {
+        Cli::command().error(ErrorKind::InvalidValue, format!("day {} of {} isn't registered", args.day, args.year)).exit();
+    };
+    let solution = args.params.apply(vec![solution])[0];
+    if let Err(e) = watch::watch(solution, &Inputs::new(dir), args.timeout.timeouts().limit(args.day)) {
+        eprintln!("{} {e}", "error:".red());
+        process::exit(2);
+    }
+}
+
+fn open_dashboard(dir: &Path, args: DashboardArgs) {
+    if let Err(e) = dashboard::open(Inputs::new(dir), args.timeout.timeouts()) {
+        eprintln!("{} {e}", "error:".red());
+        process::exit(2);
+    }
+}
+
+fn new(inputs: &Path, args: NewArgs) {
+    match scaffold::new_day(Path::new("."), inputs, args.year, args.day) {
+        Ok(changed) => {
+            for path in changed {
+                println!("{} {}", "wrote".green(), path.display());
+            }
+        }
+        Err(e) => {
+            eprintln!("{} {e}", "error:".red());
+            process::exit(2);
+        }
+    }
+}
+
+fn child(args: ChildArgs) {
+    let Some(solution) = crate::registry().get(args.year, args.day) else {
+        Cli::command().error(ErrorKind::InvalidValue, format!("day {} of {} isn't registered", args.day, args.year)).exit();
+    };
+    let solution = Tuned::leak(solution, args.params.into_iter().map(|param| (param.name, param.value)).collect());
+    if args.alloc_stats {
+        alloc::enable();
+    }
+    let mut input = String::new();
+    let solved = io::stdin()
+        .read_to_string(&mut input)
+        .and_then(|_| isolate::child(solution, &input, args.part, args.timeout));
+    if let Err(e) = solved {
+        eprintln!("{} {e}", "error:".red());
+        process::exit(2);
+    }
+}
@@ -0,0 +1,320 @@
+use std::{collections::BTreeMap, io, sync::mpsc::{self, Receiver, TryRecvError}, thread, time::Duration};
+
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, Cell, Paragraph, Row, Table, TableState},
+    DefaultTerminal, Frame,
+};
+
+use crate::{answer::Answer, answers::{AnswerFile, Verdict}, error::AocError, fixtures::Fixture, input::Inputs, isolate::{self, Limits},
+    params::Tuned, runner::PartResult, solution::{Part, Solution}, watchdog::Timeouts};
+
+/// Which input days are run on
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Source {
+    Real,
+    Example,
+}
+
+impl Source {
+    fn name(self) -> &'static str {
+        match self {
+            Source::Real => "real input",
+            Source::Example => "example input",
+        }
+    }
+}
+
+/// The latest results of a day on one of its inputs
+#[derive(Default)]
+struct LastRun {
+    parts: BTreeMap<Part, (PartResult, Verdict)>,
+    /// How each part went, followed by everything the day printed while it ran
+    log: String,
+}
+
+struct DayRow {
+    solution: &'static dyn Solution,
+    real: LastRun,
+    example: LastRun,
+}
+
+impl DayRow {
+    fn last_run(&self, source: Source) -> &LastRun {
+        match source {
+            Source::Real => &self.real,
+            Source::Example => &self.example,
+        }
+    }
+}
+
+/// A run that finished in the background
+struct Finished {
+    row: usize,
+    source: Source,
+    results: Vec<PartResult>,
+    expected: BTreeMap<Part, Answer>,
+    log: String,
+}
+
+/// A run going on in the background, and the row and input it's for
+struct Running {
+    row: usize,
+    source: Source,
+    receiver: Receiver<Finished>,
+}
+
+/// Every registered day with its stored answers and the results of runs made from the dashboard
+///
+/// Days run in a child process like `--isolate` does, so whatever they print ends up in their
+/// log instead of over the screen, and the dashboard keeps responding while they run.
+pub struct Dashboard {
+    rows: Vec<DayRow>,
+    answers: BTreeMap<u32, AnswerFile>,
+    inputs: Inputs,
+    timeouts: Timeouts,
+    table: TableState,
+    source: Source,
+    show_log: bool,
+    running: Option<Running>,
+    status: String,
+}
+
+impl Dashboard {
+    pub fn new(inputs: Inputs, timeouts: Timeouts) -> io::Result<Dashboard> {
+        let registry = crate::registry();
+        let mut answers = BTreeMap::new();
+        for year in registry.years() {
+            answers.insert(year, AnswerFile::load(year)?);
+        }
+        Ok(Dashboard {
+            rows: registry.iter().map(|solution| DayRow { solution, real: LastRun::default(), example: LastRun::default() }).collect(),
+            answers,
+            inputs,
+            timeouts,
+            table: TableState::default().with_selected(Some(0)),
+            source: Source::Real,
+            show_log: false,
+            running: None,
+            status: String::new(),
+        })
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        loop {
+            self.collect_finished();
+            terminal.draw(|frame| self.draw(frame))?;
+            if !event::poll(Duration::from_millis(100))? {
+                continue;
+            }
+            let Event::Key(key) = event::read()? else { continue };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Up | KeyCode::Char('k') => self.table.select_previous(),
+                KeyCode::Down | KeyCode::Char('j') => self.table.select_next(),
+                KeyCode::Enter | KeyCode::Char('r') => self.start(None),
+                KeyCode::Char('1') => self.start(Some(Part::One)),
+                KeyCode::Char('2') => self.start(Some(Part::Two)),
+                KeyCode::Char('e') => {
+                    self.source = match self.source {
+                        Source::Real => Source::Example,
+                        Source::Example => Source::Real,
+                    };
+                }
+                KeyCode::Char('l') => self.show_log = !self.show_log,
+                _ => (),
+            }
+        }
+    }
+
+    fn selected(&self) -> usize {
+        self.table.selected().unwrap_or(0).min(self.rows.len() - 1)
+    }
+
+    /// Starts running the selected day in the background, unless something is running already
+    fn start(&mut self, part: Option<Part>) {
+        let row = self.selected();
+        let solution = self.rows[row].solution;
+        let (year, day) = (solution.year(), solution.day());
+        if self.running.is_some() {
+            self.status = String::from("Wait for the current run to finish first");
+            return;
+        }
+        if part.is_some_and(|part| !solution.parts().contains(&part)) {
+            self.status = format!("Day {day} has no part 2");
+            return;
+        }
+
+        let (solution, input, expected) = match self.source {
+            Source::Real => match self.inputs.read(year, day) {
+                Ok(input) => {
+                    let expected = solution.parts().into_iter()
+                        .filter_map(|part| Some((part, self.answers.get(&year)?.get(day, part)?.clone())))
+                        .collect();
+                    (solution, input, expected)
+                }
+                Err(e) => {
+                    self.status = e.to_string();
+                    return;
+                }
+            },
+            Source::Example => match Fixture::load(year, day) {
+                Ok(fixture) => (Tuned::leak(solution, fixture.params), fixture.input, fixture.expected),
+                Err(e) => {
+                    self.status = e.to_string();
+                    return;
+                }
+            },
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        let source = self.source;
+        let timeout = self.timeouts.limit(day);
+        thread::spawn(move || {
+            let (results, log) = isolate::solve_capturing(solution, &input, part, timeout, Limits::default());
+            let _ = sender.send(Finished { row, source, results, expected, log });
+        });
+        self.running = Some(Running { row, source, receiver });
+        self.status = format!("Running day {day} of {year} on its {}...", source.name());
+    }
+
+    /// Stores the results of a background run if it has finished
+    fn collect_finished(&mut self) {
+        let Some(running) = &self.running else { return };
+        let finished = match running.receiver.try_recv() {
+            Ok(finished) => finished,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => {
+                self.running = None;
+                self.status = String::from("The run stopped without any results");
+                return;
+            }
+        };
+        self.running = None;
+        let row = &mut self.rows[finished.row];
+        let last = match finished.source {
+            Source::Real => &mut row.real,
+            Source::Example => &mut row.example,
+        };
+        let total = finished.results.iter().map(|result| result.elapsed + result.parse.unwrap_or_default()).sum::<Duration>();
+        last.log = run_log(&finished.results, &finished.log);
+        for result in finished.results {
+            let verdict = Verdict::check(finished.expected.get(&result.part), &result.answer);
+            last.parts.insert(result.part, (result, verdict));
+        }
+        self.status = format!("Day {} finished in {total:.2?}", row.solution.day());
+    }
+
+    /// Draws the day table, the selected day's log when it's open, and a line of help
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let log_height = if self.show_log { Constraint::Percentage(40) } else { Constraint::Length(0) };
+        let [table_area, log_area, status_area] =
+            Layout::vertical([Constraint::Min(5), log_height, Constraint::Length(1)]).areas(frame.area());
+
+        let header = Row::new(["Year", "Day", "Stars", "Part 1", "Time", "", "Part 2", "Time", ""]).bold();
+        // The row being run, which stays put however the selection moves
+        let running = self.running.as_ref().filter(|running| running.source == self.source).map(|running| running.row);
+        let rows = self.rows.iter().enumerate().map(|(idx, row)| {
+            let (year, day) = (row.solution.year(), row.solution.day());
+            let stored = self.answers.get(&year);
+            let stars = row.solution.parts().into_iter()
+                .map(|part| match stored.and_then(|file| file.get(day, part)) {
+                    Some(_) => Span::styled("★", Style::new().fg(Color::Yellow)),
+                    None => Span::styled("☆", Style::new().fg(Color::DarkGray)),
+                })
+                .collect::<Line>();
+            let mut cells = vec![Cell::from(year.to_string()), Cell::from(day.to_string()), Cell::from(stars)];
+            for part in [Part::One, Part::Two] {
+                cells.extend(part_cells(row.last_run(self.source).parts.get(&part), running == Some(idx)));
+            }
+            Row::new(cells)
+        });
+        let widths = [
+            Constraint::Length(5), Constraint::Length(4), Constraint::Length(6),
+            Constraint::Fill(1), Constraint::Length(10), Constraint::Length(9),
+            Constraint::Fill(1), Constraint::Length(10), Constraint::Length(9),
+        ];
+        let table = Table::new(rows, widths)
+            .header(header)
+            .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+            .block(Block::bordered().title(format!(" Advent of Code, {} ", self.source.name())));
+        frame.render_stateful_widget(table, table_area, &mut self.table);
+
+        if self.show_log {
+            let row = &self.rows[self.selected()];
+            let log = match row.last_run(self.source).log.as_str() {
+                "" => "Run the day to see how it went",
+                log => log,
+            };
+            // Keeps the end of the log in view, like a terminal would
+            let scroll = (log.lines().count() as u16).saturating_sub(log_area.height.saturating_sub(2));
+            let title = format!(" Day {} log, {} ", row.solution.day(), self.source.name());
+            frame.render_widget(Paragraph::new(log).scroll((scroll, 0)).block(Block::bordered().title(title)), log_area);
+        }
+
+        let help = "↑↓ select  enter run  1/2 run a part  e real/example  l log  q quit";
+        let status = if self.status.is_empty() { help.to_string() } else { format!("{}  |  {help}", self.status) };
+        frame.render_widget(Paragraph::new(status).dark_gray(), status_area);
+    }
+}
+
+/// The log of a finished run: the parse and part timings, any error, panic or limit a part
+/// ran into, and then what the day printed
+fn run_log(results: &[PartResult], printed: &str) -> String {
+    let mut log = String::new();
+    for result in results {
+        if let Some(parse) = result.parse {
+            log.push_str(&format!("Parsed the input in {parse:.2?}\n"));
+        }
+        match &result.answer {
+            Ok(answer) => log.push_str(&format!("Part {} gave {} in {:.2?}\n", result.part, answer.one_line(), result.elapsed)),
+            Err(e) => log.push_str(&format!("Part {} failed after {:.2?}: {e}\n", result.part, result.elapsed)),
+        }
+        if let Some(alloc) = result.alloc {
+            log.push_str(&format!("  {alloc}\n"));
+        }
+    }
+    if printed.trim().is_empty() {
+        log.push_str("The day printed nothing");
+    } else {
+        log.push('\n');
+        log.push_str(printed);
+    }
+    log
+}
+
+/// The answer, time and verdict cells for one part
+fn part_cells(last: Option<&(PartResult, Verdict)>, running: bool) -> [Cell<'static>; 3] {
+    let Some((result, verdict)) = last else {
+        let time = if running { "..." } else { "" };
+        return [Cell::from(""), Cell::from(time), Cell::from("")];
+    };
+    let answer = match &result.answer {
+        Ok(answer) => Cell::from(answer.one_line()),
+        Err(AocError::Timeout { limit, .. }) => Cell::from(format!("gave up after {limit:?}")),
+        Err(e) => Cell::from(e.to_string()).red(),
+    };
+    let time = if running { String::from("...") } else { format!("{:.2?}", result.elapsed) };
+    let color = match verdict {
+        Verdict::Pass => Color::Green,
+        Verdict::Mismatch | Verdict::Failed => Color::Red,
+        Verdict::Missing => Color::Yellow,
+        Verdict::Timeout | Verdict::OverLimit => Color::Magenta,
+    };
+    [answer, Cell::from(time), Cell::from(verdict.to_string()).fg(color)]
+}
+
+/// Opens the dashboard, taking over the terminal until it's closed
+pub fn open(inputs: Inputs, timeouts: Timeouts) -> io::Result<()> {
+    let mut dashboard = Dashboard::new(inputs, timeouts)?;
+    let mut terminal = ratatui::init();
+    let result = dashboard.run(&mut terminal);
+    ratatui::restore();
+    result
+}
@@ -0,0 +1,68 @@
+use std::{fmt::Display, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+/// Why a day couldn't produce an answer, short of a bug in the solution
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AocError {
+    /// The input isn't laid out the way the day expects
+    Parse {
+        day: u32,
+        /// Starting from 1, like an editor shows it
+        line: usize,
+        /// Starting from 1, counted in characters
+        column: usize,
+        expected: String,
+        found: String,
+    },
+    /// The input was read fine, but the puzzle has no answer for it
+    NoAnswer { day: u32, reason: String },
+    /// The part was still running when its time limit ran out
+    Timeout { day: u32, limit: Duration },
+    /// The part panicked, which is always a bug in the solution
+    Panic { day: u32, message: String, location: String },
+    /// The day's process went over one of the limits it was run with, like `1024 MiB of memory`
+    LimitExceeded { day: u32, limit: String },
+    /// The day's process died before it could report back
+    Crashed { day: u32, reason: String },
+    /// A parameter override the day doesn't have, or whose value doesn't fit it
+    InvalidParam { day: u32, name: String, reason: String },
+}
+
+pub type Result<T, E = AocError> = std::result::Result<T, E>;
+
+impl AocError {
+    pub fn no_answer(day: u32, reason: impl Into<String>) -> AocError {
+        AocError::NoAnswer { day, reason: reason.into() }
+    }
+
+    pub fn day(&self) -> u32 {
+        match self {
+            AocError::Parse { day, .. }
+            | AocError::NoAnswer { day, .. }
+            | AocError::Timeout { day, .. }
+            | AocError::Panic { day, .. }
+            | AocError::LimitExceeded { day, .. }
+            | AocError::Crashed { day, .. }
+            | AocError::InvalidParam { day, .. } => *day,
+        }
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::Parse { day, line, column, expected, found } => {
+                write!(f, "day {day}, line {line}, column {column}: expected {expected}, found {found}")
+            }
+            AocError::NoAnswer { day, reason } => write!(f, "day {day}: {reason}"),
+            AocError::Timeout { day, limit } => write!(f, "day {day}: gave up after {limit:?}"),
+            AocError::Panic { day, message, location } => write!(f, "day {day} panicked at {location}: {message}"),
+            AocError::LimitExceeded { day, limit } => write!(f, "day {day}: went over its limit of {limit}"),
+            AocError::Crashed { day, reason } => write!(f, "day {day}: its process {reason}"),
+            AocError::InvalidParam { day, name, reason } => write!(f, "day {day}: parameter {name} {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
@@ -0,0 +1,65 @@
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+use crate::{answer::Answer, answers::answer_from_toml, params::Overrides, solution::Part};
+
+/// A puzzle's worked example, stored in `fixtures/<year>/day<N>.toml`
+///
+/// Holds the example input, the answers the puzzle text gives for it, and any of the day's
+/// parameters that need changing to fit the smaller example:
+///
+/// ```toml
+/// input = '''
+/// Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+/// ...'''
+/// part1 = 26
+/// part2 = 56000011
+///
+/// [params]
+/// COLUMN_AT = 10
+/// RANGE = 20
+/// ```
+///
+/// A part without an expected answer isn't checked.
+#[derive(Debug)]
+pub struct Fixture {
+    pub input: String,
+    pub expected: BTreeMap<Part, Answer>,
+    /// Passed to [`Solution::solve_with`](crate::solution::Solution::solve_with)
+    pub params: Overrides,
+}
+
+impl Fixture {
+    pub fn path(year: u32, day: u32) -> PathBuf {
+        PathBuf::from(format!("./fixtures/{year}/day{day}.toml"))
+    }
+
+    pub fn load(year: u32, day: u32) -> io::Result<Fixture> {
+        let path = Fixture::path(year, day);
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {msg}", path.display()));
+
+        let table = fs::read_to_string(&path)?.parse::<toml::Table>().map_err(|e| invalid(e.to_string()))?;
+        let mut fixture = Fixture { input: String::new(), expected: BTreeMap::new(), params: Overrides::new() };
+        for (key, value) in &table {
+            match (key.as_str(), value) {
+                // Inputs don't end in a newline, but it's tidier to close the string on its own line
+                ("input", toml::Value::String(input)) => fixture.input = input.trim_end_matches('\n').to_string(),
+                ("part1" | "part2", value) => {
+                    let part = if key == "part1" { Part::One } else { Part::Two };
+                    let answer = answer_from_toml(value).ok_or_else(|| invalid(format!("{key} isn't a number or string")))?;
+                    fixture.expected.insert(part, answer);
+                }
+                ("params", toml::Value::Table(params)) => {
+                    for (name, value) in params {
+                        let value = value.as_integer().ok_or_else(|| invalid(format!("param {name} isn't an integer")))?;
+                        fixture.params.insert(name.to_uppercase(), value);
+                    }
+                }
+                _ => return Err(invalid(format!("unexpected key {key:?}"))),
+            }
+        }
+        if !table.contains_key("input") {
+            return Err(invalid(String::from("no input")));
+        }
+        Ok(fixture)
+    }
+}
@@ -0,0 +1,207 @@
+use std::{collections::BTreeMap, env, fs::{self, OpenOptions}, io::{self, Write}, path::Path, process::Command, thread, time::{Duration, SystemTime, UNIX_EPOCH}};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{bench::Stats, params::Overrides, solution::{Part, Solution}};
+
+/// Where `bench` keeps its results unless told otherwise
+pub const DEFAULT_PATH: &str = "bench-history.jsonl";
+
+/// The machine a benchmark ran on
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Machine {
+    pub host: String,
+    /// The CPU's model name, where the OS says what it is
+    pub cpu: Option<String>,
+    pub cores: usize,
+    pub os: String,
+    pub arch: String,
+}
+
+impl Machine {
+    pub fn current() -> Machine {
+        Machine {
+            host: hostname().unwrap_or_else(|| String::from("unknown")),
+            cpu: cpu_model(),
+            cores: thread::available_parallelism().map_or(1, |n| n.get()),
+            os: env::consts::OS.to_string(),
+            arch: env::consts::ARCH.to_string(),
+        }
+    }
+}
+
+fn hostname() -> Option<String> {
+    let mut buf = [0u8; 256];
+    // SAFETY: the buffer is valid for its whole length, and one byte is kept back so the name
+    // is always terminated
+    if unsafe { libc::gethostname(buf.as_mut_ptr().cast(), buf.len() - 1) } != 0 {
+        return None;
+    }
+    let len = buf.iter().position(|&b| b == 0)?;
+    String::from_utf8(buf[..len].to_vec()).ok()
+}
+
+fn cpu_model() -> Option<String> {
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok()?;
+    let line = cpuinfo.lines().find(|line| line.starts_with("model name"))?;
+    Some(line.split_once(':')?.1.trim().to_string())
+}
+
+/// What a `bench` run was measuring: the code, the compiler that built it and the machine
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Context {
+    /// When the run started, in milliseconds since the Unix epoch. Every part benchmarked in
+    /// the same run shares it.
+    pub run: u64,
+    /// The commit checked out, if the benchmarks ran inside a git repository
+    pub commit: Option<String>,
+    /// Whether there were uncommitted changes on top of `commit`
+    pub dirty: bool,
+    pub rustc: String,
+    pub machine: Machine,
+}
+
+impl Context {
+    pub fn current() -> Context {
+        let run = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_millis() as u64);
+        let git = |args: &[&str]| {
+            let output = Command::new("git").args(args).output().ok().filter(|output| output.status.success())?;
+            String::from_utf8(output.stdout).ok().map(|out| out.trim().to_string())
+        };
+        Context {
+            run,
+            commit: git(&["rev-parse", "--short", "HEAD"]),
+            dirty: git(&["status", "--porcelain", "--untracked-files=no"]).is_some_and(|status| !status.is_empty()),
+            rustc: env!("AOC_RUSTC_VERSION").to_string(),
+            machine: Machine::current(),
+        }
+    }
+
+    /// The commit, compiler and machine, for saying which run is which
+    pub fn describe(&self) -> String {
+        let commit = self.commit.as_deref().unwrap_or("no commit");
+        let dirty = if self.dirty { " with changes" } else { "" };
+        format!("{commit}{dirty}, {} on {}", self.rustc, self.machine.host)
+    }
+}
+
+/// One part's benchmark, as a line in the history file
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    #[serde(flatten)]
+    pub context: Context,
+    pub year: u32,
+    pub day: u32,
+    pub part: u8,
+    /// The `--param` overrides the day ran with, as a run with different parameters times
+    /// a different puzzle
+    #[serde(default, skip_serializing_if = "Overrides::is_empty")]
+    pub params: Overrides,
+    pub iterations: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+    pub p95_ns: u64,
+    pub max_ns: u64,
+}
+
+impl Entry {
+    pub fn new(context: &Context, solution: &dyn Solution, part: Part, stats: &Stats) -> Entry {
+        let ns = |duration: Duration| u64::try_from(duration.as_nanos()).unwrap_or(u64::MAX);
+        Entry {
+            context: context.clone(),
+            year: solution.year(),
+            day: solution.day(),
+            part: part.number(),
+            params: solution.overrides(),
+            iterations: stats.iterations,
+            min_ns: ns(stats.min),
+            median_ns: ns(stats.median),
+            mean_ns: ns(stats.mean),
+            p95_ns: ns(stats.p95),
+            max_ns: ns(stats.max),
+        }
+    }
+
+    fn key(&self) -> (u32, u32, u8, &Overrides) {
+        (self.year, self.day, self.part, &self.params)
+    }
+}
+
+/// Adds entries to the end of the history file, creating it if there isn't one yet
+pub fn append(path: &Path, entries: &[Entry]) -> io::Result<()> {
+    let mut lines = Vec::new();
+    for entry in entries {
+        serde_json::to_writer(&mut lines, entry)?;
+        lines.push(b'\n');
+    }
+    // One write, so an interrupted run can't leave half a line behind
+    OpenOptions::new().create(true).append(true).open(path)?.write_all(&lines)
+}
+
+/// Reads every entry in the history file, oldest first
+pub fn load(path: &Path) -> io::Result<Vec<Entry>> {
+    let text = fs::read_to_string(path)?;
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(idx, line)| {
+            serde_json::from_str(line).map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("{}:{}: {e}", path.display(), idx + 1))
+            })
+        })
+        .collect()
+}
+
+/// The latest run before `before` (when given) on a commit starting with `commit` (when given)
+pub fn find_run<'a>(entries: &'a [Entry], commit: Option<&str>, before: Option<u64>) -> Option<&'a Context> {
+    entries
+        .iter()
+        .map(|entry| &entry.context)
+        .filter(|context| before.is_none_or(|before| context.run < before))
+        .filter(|context| commit.is_none_or(|commit| context.commit.as_deref().is_some_and(|c| c.starts_with(commit))))
+        .max_by_key(|context| context.run)
+}
+
+/// How one part's median time changed between two runs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Change {
+    pub year: u32,
+    pub day: u32,
+    pub part: u8,
+    pub baseline: Duration,
+    pub current: Duration,
+}
+
+impl Change {
+    /// The change in time as a percentage of the baseline, negative when it got faster
+    pub fn percent(&self) -> f64 {
+        (self.current.as_secs_f64() / self.baseline.as_secs_f64() - 1.0) * 100.0
+    }
+
+    /// Whether the part slowed down by more than `threshold` percent
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent() > threshold
+    }
+}
+
+/// Compares the median time of every part the two runs both benchmarked with the same params
+pub fn compare(entries: &[Entry], baseline: u64, current: u64) -> Vec<Change> {
+    let run = |run: u64| {
+        entries.iter().filter(|entry| entry.context.run == run).map(|entry| (entry.key(), entry)).collect::<BTreeMap<_, _>>()
+    };
+    let baseline = run(baseline);
+    run(current)
+        .into_iter()
+        .filter_map(|(key, current)| {
+            let baseline = baseline.get(&key)?;
+            Some(Change {
+                year: key.0,
+                day: key.1,
+                part: key.2,
+                baseline: Duration::from_nanos(baseline.median_ns),
+                current: Duration::from_nanos(current.median_ns),
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,85 @@
+use std::{collections::BTreeMap, fmt::Display, fs, io::{self, Read}, path::{Path, PathBuf}, sync::OnceLock};
+
+/// Where puzzle inputs are read from
+///
+/// Inputs live at `<root>/<year>/day<N>.txt` unless a day has been given its own path.
+/// A path of `-` reads standard input instead.
+#[derive(Debug)]
+pub struct Inputs {
+    root: PathBuf,
+    overrides: BTreeMap<u32, PathBuf>,
+    stdin: OnceLock<String>,
+}
+
+impl Default for Inputs {
+    fn default() -> Inputs {
+        Inputs::new("inputs")
+    }
+}
+
+impl Inputs {
+    pub fn new(root: impl Into<PathBuf>) -> Inputs {
+        Inputs { root: root.into(), overrides: BTreeMap::new(), stdin: OnceLock::new() }
+    }
+
+    /// Reads `day` from `path` instead, in every year
+    pub fn with_override(mut self, day: u32, path: impl Into<PathBuf>) -> Inputs {
+        self.overrides.insert(day, path.into());
+        self
+    }
+
+    pub fn path(&self, year: u32, day: u32) -> PathBuf {
+        match self.overrides.get(&day) {
+            Some(path) => path.clone(),
+            None => self.root.join(year.to_string()).join(format!("day{day}.txt")),
+        }
+    }
+
+    /// Reads a day's input, without the newline it usually ends in
+    ///
+    /// Standard input is only read once, so several days pointed at `-` all see the same text.
+    pub fn read(&self, year: u32, day: u32) -> Result<String, InputError> {
+        let path = self.path(year, day);
+        if path == Path::new("-") {
+            if let Some(input) = self.stdin.get() {
+                return Ok(input.clone());
+            }
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input).map_err(|error| InputError::Io { path, error })?;
+            return Ok(self.stdin.get_or_init(|| trim_newline(input)).clone());
+        }
+        fs::read_to_string(&path).map(trim_newline).map_err(|error| match error.kind() {
+            io::ErrorKind::NotFound => InputError::Missing { day, path },
+            _ => InputError::Io { path, error },
+        })
+    }
+}
+
+/// Drops the one newline a downloaded or piped input ends in, as the days don't expect it
+pub fn trim_newline(mut input: String) -> String {
+    if input.ends_with('\n') {
+        input.pop();
+        if input.ends_with('\r') {
+            input.pop();
+        }
+    }
+    input
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// There's no file where the day's input should be
+    Missing { day: u32, path: PathBuf },
+    Io { path: PathBuf, error: io::Error },
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::Missing { day, path } => write!(f, "day {day} has no input at {}", path.display()),
+            InputError::Io { path, error } => write!(f, "couldn't read {}: {error}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
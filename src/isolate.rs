@@ -0,0 +1,160 @@
+use std::{env, io::{self, Write}, os::unix::process::{CommandExt, ExitStatusExt}, process::{Command, ExitStatus, Output, Stdio}, thread, time::{Duration, Instant}};
+
+use crate::{alloc, error::AocError, runner::{self, PartResult}, solution::{Part, Solution}};
+
+/// The hidden subcommand a day's child process is started with
+pub const CHILD_COMMAND: &str = "child";
+
+const MIB: u64 = 1024 * 1024;
+
+/// Resource limits put on a day's child process
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Address space, in bytes
+    pub memory: Option<u64>,
+    /// CPU time, in seconds
+    pub cpu: Option<u64>,
+}
+
+impl Limits {
+    /// Applies the limits to the current process
+    ///
+    /// This runs between `fork` and `exec`, so it mustn't allocate.
+    fn apply(&self) -> io::Result<()> {
+        let set = |resource, soft: u64, hard: u64| {
+            let limit = libc::rlimit { rlim_cur: soft as libc::rlim_t, rlim_max: hard as libc::rlim_t };
+            // SAFETY: `limit` is a valid rlimit that outlives the call
+            match unsafe { libc::setrlimit(resource, &limit) } {
+                0 => Ok(()),
+                _ => Err(io::Error::last_os_error()),
+            }
+        };
+        if let Some(bytes) = self.memory {
+            set(libc::RLIMIT_AS, bytes, bytes)?;
+        }
+        // Going over the soft limit sends SIGXCPU, and the hard one a second later makes sure
+        // the process stops even if something catches that
+        if let Some(seconds) = self.cpu {
+            set(libc::RLIMIT_CPU, seconds, seconds + 1)?;
+        }
+        Ok(())
+    }
+}
+
+/// Solves a day in a child process running under `limits`
+///
+/// The child is this executable run as `aoc child`. It reads the input from stdin and writes
+/// each part's result to stdout as a line of JSON as soon as it has one, so the parts it
+/// finished are kept if it dies partway through. The parts it never reported get the reason it
+/// died.
+pub fn solve(solution: &dyn Solution, input: &str, part: Option<Part>, timeout: Option<Duration>, limits: Limits) -> Vec<PartResult> {
+    let (results, log) = solve_capturing(solution, input, part, timeout, limits);
+    io::stderr().write_all(log.as_bytes()).ok();
+    results
+}
+
+/// Like [`solve`], but returns everything else the child printed instead of passing it on
+pub fn solve_capturing(solution: &dyn Solution, input: &str, part: Option<Part>, timeout: Option<Duration>,
+    limits: Limits) -> (Vec<PartResult>, String) {
+    let day = solution.day();
+    let start = Instant::now();
+    let (mut results, log, error) = match spawn(solution, input, part, timeout, limits) {
+        Ok(output) => {
+            let (results, mut log) = read_results(&output);
+            log.push_str(&String::from_utf8_lossy(&output.stderr));
+            (results, log, exit_reason(day, output.status, &output.stderr, limits))
+        }
+        Err(e) => (Vec::new(), String::new(), AocError::Crashed { day, reason: format!("couldn't be started: {e}") }),
+    };
+
+    let reported = results.iter().map(|result| result.elapsed + result.parse.unwrap_or_default()).sum::<Duration>();
+    let missing = runner::selected_parts(solution, part).into_iter().skip(results.len()).collect::<Vec<_>>();
+    for (idx, part) in missing.into_iter().enumerate() {
+        // Whatever time the child spent that it didn't report went on the part it died in
+        let elapsed = if idx == 0 { Instant::now().duration_since(start).saturating_sub(reported) } else { Duration::ZERO };
+        results.push(PartResult { part, answer: Err(error.clone()), elapsed, parse: None, alloc: None });
+    }
+    (results, log)
+}
+
+fn spawn(solution: &dyn Solution, input: &str, part: Option<Part>, timeout: Option<Duration>, limits: Limits) -> io::Result<Output> {
+    let mut command = Command::new(env::current_exe()?);
+    command.args([CHILD_COMMAND, &solution.year().to_string(), &solution.day().to_string()]);
+    if let Some(part) = part {
+        command.arg("--part").arg(part.to_string());
+    }
+    if let Some(timeout) = timeout {
+        command.arg("--timeout").arg(format!("{}s", timeout.as_secs_f64()));
+    }
+    if alloc::enabled() {
+        command.arg("--alloc-stats");
+    }
+    for (name, value) in solution.overrides() {
+        command.arg("--param").arg(format!("{name}={value}"));
+    }
+    command.stdin(Stdio::piped()).stdout(Stdio::piped()).stderr(Stdio::piped());
+    // SAFETY: `apply` only calls setrlimit, which is async-signal-safe
+    unsafe {
+        command.pre_exec(move || limits.apply());
+    }
+
+    let mut child = command.spawn()?;
+    let mut stdin = child.stdin.take().expect("the child's stdin is piped");
+    let input = input.to_string();
+    // Writing from another thread means a child that stops reading can't hold us up, and
+    // dropping stdin afterwards lets it see the end of the input
+    let writer = thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child.wait_with_output()?;
+    match writer.join().expect("the input writer panicked") {
+        Err(e) if e.kind() != io::ErrorKind::BrokenPipe => Err(e),
+        _ => Ok(output),
+    }
+}
+
+/// The results the child sent back, and anything else it printed to stdout
+fn read_results(output: &Output) -> (Vec<PartResult>, String) {
+    let mut results = Vec::new();
+    let mut log = String::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        match serde_json::from_str(line) {
+            Ok(result) => results.push(result),
+            Err(_) => {
+                log.push_str(line);
+                log.push('\n');
+            }
+        }
+    }
+    (results, log)
+}
+
+/// Why the child stopped before reporting every part
+fn exit_reason(day: u32, status: ExitStatus, stderr: &[u8], limits: Limits) -> AocError {
+    match (status.signal(), limits) {
+        (Some(libc::SIGXCPU | libc::SIGKILL), Limits { cpu: Some(seconds), .. }) => {
+            AocError::LimitExceeded { day, limit: format!("{seconds}s of CPU time") }
+        }
+        // Rust aborts when an allocation fails, after saying so on stderr
+        (Some(libc::SIGABRT), Limits { memory: Some(bytes), .. })
+            if String::from_utf8_lossy(stderr).contains("memory allocation of") =>
+        {
+            AocError::LimitExceeded { day, limit: format!("{} MiB of memory", bytes / MIB) }
+        }
+        (Some(signal), _) => AocError::Crashed { day, reason: format!("was killed by signal {signal}") },
+        (None, _) => AocError::Crashed { day, reason: format!("stopped early with {status}") },
+    }
+}
+
+/// Runs in the child: solves each part and writes its result to stdout as a line of JSON
+pub fn child(solution: &'static dyn Solution, input: &str, part: Option<Part>, timeout: Option<Duration>) -> io::Result<()> {
+    let mut written = Ok(());
+    runner::solve_each(solution, input, part, timeout, |result| {
+        if written.is_ok() {
+            // Only locked while writing, since a part that timed out may still be printing
+            let mut stdout = io::stdout().lock();
+            written = serde_json::to_writer(&mut stdout, &result).map_err(io::Error::from)
+                .and_then(|()| writeln!(stdout))
+                .and_then(|()| stdout.flush());
+        }
+    });
+    written
+}
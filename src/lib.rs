@@ -1,9 +1,35 @@
-#![feature(iter_next_chunk)]
 #![feature(exact_size_is_empty)]
 #![feature(int_roundings)]
 
 pub mod aoc2022;
+pub mod alloc;
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod dashboard;
+pub mod error;
+pub mod fixtures;
+pub mod history;
+pub mod input;
+pub mod isolate;
+pub mod panics;
+pub mod params;
+pub mod parse;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
 pub mod utils;
+pub mod watch;
+pub mod watchdog;
 
 #[cfg(test)]
-pub mod test_utils;
\ No newline at end of file
+pub mod test_utils;
+
+/// Builds the registry of every solved day across all years
+pub fn registry() -> solution::Registry {
+    let mut registry = solution::Registry::new();
+    aoc2022::register(&mut registry);
+    registry
+}
\ No newline at end of file
@@ -1,5 +1,9 @@
-use aoc::*;
+use aoc::{alloc::CountingAlloc, cli::Cli};
+use clap::Parser;
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
 
 fn main() {
-    aoc2022::main();
+    Cli::parse().run();
 }
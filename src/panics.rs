@@ -0,0 +1,38 @@
+use std::{cell::{Cell, RefCell}, panic::{self, AssertUnwindSafe}, sync::Once};
+
+use crate::error::{AocError, Result};
+
+thread_local! {
+    /// Whether a panic on this thread is about to be caught by [`catch`]
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    /// The message and location of the last panic [`catch`] caught on this thread
+    static CAUGHT: RefCell<Option<(String, String)>> = const { RefCell::new(None) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// Runs `f`, turning a panic into an [`AocError::Panic`] for `day` instead of unwinding further
+///
+/// Panics caught here aren't printed. Panics anywhere else still go to the usual hook.
+pub fn catch<T>(day: u32, f: impl FnOnce() -> Result<T>) -> Result<T> {
+    INSTALL_HOOK.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.get() {
+                let message = info.payload_as_str().unwrap_or("Box<dyn Any>").to_string();
+                let location = info.location().map_or(String::from("an unknown location"), |l| l.to_string());
+                CAUGHT.set(Some((message, location)));
+            } else {
+                default(info);
+            }
+        }));
+    });
+
+    let was_catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(was_catching);
+    result.unwrap_or_else(|_| {
+        let (message, location) = CAUGHT.take().unwrap_or_else(|| (String::from("unknown panic"), String::from("an unknown location")));
+        Err(AocError::Panic { day, message, location })
+    })
+}
@@ -0,0 +1,144 @@
+use std::{collections::BTreeMap, sync::{Mutex, PoisonError}};
+
+use crate::{answer::Answer, error::{AocError, Result}, solution::{Parsed, Part, Solution}};
+
+/// Parameter values to use instead of a day's defaults, keyed by their uppercase names
+pub type Overrides = BTreeMap<String, i64>;
+
+/// A day's puzzle parameters, declared with [`params!`](crate::params!)
+pub trait ParamSet: Default {
+    /// Every parameter's name, in uppercase, with its default value
+    fn defaults() -> Vec<(String, i64)>;
+
+    /// Changes the parameter called `name`, in any case
+    fn set(&mut self, day: u32, name: &str, value: i64) -> Result<()>;
+
+    /// The defaults, with `overrides` applied on top
+    fn with_overrides(day: u32, overrides: &Overrides) -> Result<Self> {
+        let mut params = Self::default();
+        for (name, &value) in overrides {
+            params.set(day, name, value)?;
+        }
+        Ok(params)
+    }
+}
+
+/// Declares a day's puzzle parameters as a `Params` struct with their defaults
+///
+/// Each parameter is a field with the value the real puzzle uses as its default. Overrides
+/// refer to a field by its name in uppercase, so `column_at` is set with `COLUMN_AT=10`.
+/// A range in brackets after the type limits what it can be set to, for parameters the day
+/// can't cope with every value of.
+///
+/// ```
+/// use aoc::params::ParamSet;
+///
+/// aoc::params! {
+///     /// The row part 1 looks at
+///     column_at: isize = 2000000,
+///     /// Minutes the search runs for
+///     time_limit: u8 [1..=32] = 24,
+/// }
+///
+/// let params = Params::with_overrides(15, &[(String::from("COLUMN_AT"), 10)].into()).unwrap();
+/// assert_eq!(params.column_at, 10);
+/// let error = Params::with_overrides(15, &[(String::from("TIME_LIMIT"), 0)].into()).unwrap_err();
+/// assert_eq!(error.to_string(), "day 15: parameter TIME_LIMIT has to be in 1..=32");
+/// ```
+#[macro_export]
+macro_rules! params {
+    ($($(#[doc = $doc:literal])* $field:ident: $ty:ty $([$range:expr])? = $default:expr),* $(,)?) => {
+        /// This day's puzzle parameters, with the real puzzle's values as defaults
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub struct Params {
+            $($(#[doc = $doc])* pub $field: $ty,)*
+        }
+
+        impl Default for Params {
+            fn default() -> Params {
+                Params { $($field: $default,)* }
+            }
+        }
+
+        impl $crate::params::ParamSet for Params {
+            fn defaults() -> Vec<(String, i64)> {
+                let defaults = Params::default();
+                vec![$((stringify!($field).to_uppercase(), i64::try_from(defaults.$field).unwrap_or(i64::MAX)),)*]
+            }
+
+            fn set(&mut self, day: u32, name: &str, value: i64) -> $crate::error::Result<()> {
+                let invalid = |reason: String| $crate::error::AocError::InvalidParam { day, name: name.to_uppercase(), reason };
+                $(
+                    if name.eq_ignore_ascii_case(stringify!($field)) {
+                        let value = <$ty>::try_from(value).map_err(|_| invalid(format!("can't be {value}")))?;
+                        $(
+                            if !($range).contains(&value) {
+                                return Err(invalid(format!("has to be in {:?}", $range)));
+                            }
+                        )?
+                        self.$field = value;
+                        return Ok(());
+                    }
+                )*
+                Err(invalid(String::from("doesn't exist")))
+            }
+        }
+    };
+}
+
+/// A day run with some of its parameters changed
+pub struct Tuned {
+    solution: &'static dyn Solution,
+    overrides: Overrides,
+}
+
+impl Tuned {
+    /// `solution` with `overrides` applied, or just `solution` when there aren't any
+    ///
+    /// Parts may run on threads of their own, which need a `'static` solution, so the tuned one
+    /// is leaked. Asking for the same solution and overrides again gives back the one already
+    /// leaked, so commands that keep re-running a day, like `watch`, don't keep growing.
+    pub fn leak(solution: &'static dyn Solution, overrides: Overrides) -> &'static dyn Solution {
+        static LEAKED: Mutex<Vec<&'static Tuned>> = Mutex::new(Vec::new());
+
+        if overrides.is_empty() {
+            return solution;
+        }
+        let mut leaked = LEAKED.lock().unwrap_or_else(PoisonError::into_inner);
+        // Days are unit structs, so they can't be told apart by address
+        let same = |tuned: &Tuned| (tuned.solution.year(), tuned.solution.day(), tuned.solution.overrides(), &tuned.overrides)
+            == (solution.year(), solution.day(), solution.overrides(), &overrides);
+        if let Some(&tuned) = leaked.iter().find(|tuned| same(tuned)) {
+            return tuned;
+        }
+        let tuned = Box::leak(Box::new(Tuned { solution, overrides }));
+        leaked.push(tuned);
+        tuned
+    }
+}
+
+impl Solution for Tuned {
+    fn year(&self) -> u32 { self.solution.year() }
+    fn day(&self) -> u32 { self.solution.day() }
+    fn parse(&self, input: &str) -> Result<Parsed> { self.solution.parse(input) }
+    fn part1(&self, parsed: &Parsed) -> Result<Answer> { self.solution.solve_with(Part::One, parsed, &self.overrides) }
+    fn part2(&self, parsed: &Parsed) -> Result<Answer> { self.solution.solve_with(Part::Two, parsed, &self.overrides) }
+    fn has_part2(&self) -> bool { self.solution.has_part2() }
+    fn params(&self) -> Vec<(String, i64)> { self.solution.params() }
+
+    fn solve_with(&self, part: Part, parsed: &Parsed, overrides: &Overrides) -> Result<Answer> {
+        let mut merged = self.overrides.clone();
+        merged.extend(overrides.iter().map(|(name, value)| (name.clone(), *value)));
+        self.solution.solve_with(part, parsed, &merged)
+    }
+
+    fn overrides(&self) -> Overrides {
+        self.overrides.clone()
+    }
+}
+
+/// The error a day without parameters gives when asked to change one
+pub(crate) fn no_params(day: u32, overrides: &Overrides) -> Option<AocError> {
+    let name = overrides.keys().next()?;
+    Some(AocError::InvalidParam { day, name: name.to_uppercase(), reason: String::from("doesn't exist") })
+}
@@ -0,0 +1,264 @@
+//! Helpers for reading puzzle inputs that point at the exact spot an input stops making sense
+//!
+//! ```
+//! use aoc::parse::Lines;
+//!
+//! let mut lines = Lines::new(4, "2-4,6-8\n2-3,x-5");
+//! let mut cursor = lines.expect("a pair")?.cursor();
+//! assert_eq!(cursor.int::<u32>()?, 2);
+//! cursor.tag("-")?;
+//!
+//! let mut cursor = lines.expect("a pair")?.cursor();
+//! cursor.until(",")?;
+//! let error = cursor.int::<u32>().unwrap_err();
+//! assert_eq!(error.to_string(), r#"day 4, line 2, column 5: expected a number, found "x-5""#);
+//! # Ok::<(), aoc::error::AocError>(())
+//! ```
+
+use std::{fmt::Display, str::FromStr};
+
+use crate::error::{AocError, Result};
+
+/// One line of a day's input, which remembers where it came from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub day: u32,
+    /// Starting from 1
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// A cursor at the start of the line
+    pub fn cursor(self) -> Cursor<'a> {
+        Cursor { line: self, pos: 0 }
+    }
+
+    /// An error pointing at the character in `column`, counting from 1
+    pub fn error_at(&self, column: usize, expected: impl Display) -> AocError {
+        let found = match self.text.chars().nth(column - 1) {
+            Some(c) => format!("{c:?}"),
+            None => String::from("end of line"),
+        };
+        AocError::Parse { day: self.day, line: self.number, column, expected: expected.to_string(), found }
+    }
+
+    /// The line's characters, each with the column it's in
+    pub fn columns(&self) -> impl Iterator<Item = (usize, char)> + 'a {
+        self.text.chars().enumerate().map(|(idx, c)| (idx + 1, c))
+    }
+
+    /// Parses the whole line with `f`, failing if anything is left over
+    pub fn parse<T>(self, f: impl FnOnce(&mut Cursor<'a>) -> Result<T>) -> Result<T> {
+        let mut cursor = self.cursor();
+        let value = f(&mut cursor)?;
+        cursor.end()?;
+        Ok(value)
+    }
+}
+
+/// The lines of an input, or of one block of it
+#[derive(Clone, Debug)]
+pub struct Lines<'a> {
+    day: u32,
+    lines: std::vec::IntoIter<Line<'a>>,
+    /// The number of the line after the last one, where running out is reported
+    end: usize,
+    end_name: &'static str,
+}
+
+impl<'a> Lines<'a> {
+    pub fn new(day: u32, input: &'a str) -> Lines<'a> {
+        let lines = input
+            .split('\n')
+            .enumerate()
+            .map(|(idx, text)| Line { day, number: idx + 1, text })
+            .collect::<Vec<_>>();
+        Lines { day, end: lines.len() + 1, lines: lines.into_iter(), end_name: "end of input" }
+    }
+
+    /// Splits the input into blocks separated by blank lines, where a line of spaces counts
+    /// as blank
+    pub fn blocks(day: u32, input: &'a str) -> Vec<Lines<'a>> {
+        let mut blocks = Vec::new();
+        let mut block = Vec::new();
+        for line in Lines::new(day, input) {
+            if line.text.trim().is_empty() {
+                if !block.is_empty() {
+                    blocks.push(Lines::from_block(day, std::mem::take(&mut block), line.number));
+                }
+            } else {
+                block.push(line);
+            }
+        }
+        if let Some(last) = block.last() {
+            let end = last.number + 1;
+            blocks.push(Lines::from_block(day, block, end));
+        }
+        blocks
+    }
+
+    fn from_block(day: u32, lines: Vec<Line<'a>>, end: usize) -> Lines<'a> {
+        Lines { day, lines: lines.into_iter(), end, end_name: "end of block" }
+    }
+
+    /// The next line, or an error saying what should have been there
+    pub fn expect(&mut self, expected: impl Display) -> Result<Line<'a>> {
+        self.next().ok_or_else(|| AocError::Parse {
+            day: self.day,
+            line: self.end,
+            column: 1,
+            expected: expected.to_string(),
+            found: String::from(self.end_name),
+        })
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Line<'a>;
+
+    fn next(&mut self) -> Option<Line<'a>> {
+        self.lines.next()
+    }
+}
+
+/// A position within a line, moving forward as pieces of it are parsed
+#[derive(Clone, Debug)]
+pub struct Cursor<'a> {
+    line: Line<'a>,
+    /// Byte offset into the line
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn line(&self) -> Line<'a> {
+        self.line
+    }
+
+    /// The column the cursor is at, counting from 1
+    pub fn column(&self) -> usize {
+        self.line.text[..self.pos].chars().count() + 1
+    }
+
+    /// Everything not parsed yet
+    pub fn rest(&self) -> &'a str {
+        &self.line.text[self.pos..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest().is_empty()
+    }
+
+    /// An error at the cursor, showing the next few characters as what was found instead
+    pub fn error(&self, expected: impl Display) -> AocError {
+        let rest = self.rest();
+        let found = match rest.char_indices().nth(16) {
+            _ if rest.is_empty() => String::from("end of line"),
+            Some((idx, _)) => format!("{:?}", format!("{}...", &rest[..idx])),
+            None => format!("{rest:?}"),
+        };
+        AocError::Parse { day: self.line.day, line: self.line.number, column: self.column(), expected: expected.to_string(), found }
+    }
+
+    /// Skips past `tag`, which has to come next
+    pub fn tag(&mut self, tag: &str) -> Result<()> {
+        if !self.rest().starts_with(tag) {
+            return Err(self.error(format!("{tag:?}")));
+        }
+        self.pos += tag.len();
+        Ok(())
+    }
+
+    /// Skips past `tag` if it comes next, saying whether it did
+    pub fn optional(&mut self, tag: &str) -> bool {
+        self.tag(tag).is_ok()
+    }
+
+    /// Reads an integer, with an optional sign
+    pub fn int<T: FromStr>(&mut self) -> Result<T> {
+        let rest = self.rest();
+        let sign = usize::from(rest.starts_with(['-', '+']));
+        let len = sign + rest[sign..].bytes().take_while(u8::is_ascii_digit).count();
+        if len == sign {
+            return Err(self.error("a number"));
+        }
+        match rest[..len].parse() {
+            Ok(n) => {
+                self.pos += len;
+                Ok(n)
+            }
+            Err(_) => Err(self.error(format!("a number that fits in {}", std::any::type_name::<T>()))),
+        }
+    }
+
+    /// Reads a run of letters and digits
+    pub fn word(&mut self) -> Result<&'a str> {
+        let rest = self.rest();
+        let len = rest.find(|c: char| !c.is_alphanumeric()).unwrap_or(rest.len());
+        if len == 0 {
+            return Err(self.error("a word"));
+        }
+        self.pos += len;
+        Ok(&rest[..len])
+    }
+
+    /// Reads everything left on the line
+    pub fn take_rest(&mut self) -> &'a str {
+        let rest = self.rest();
+        self.pos = self.line.text.len();
+        rest
+    }
+
+    /// Reads a single character
+    pub fn char(&mut self) -> Result<char> {
+        let c = self.rest().chars().next().ok_or_else(|| self.error("a character"))?;
+        self.pos += c.len_utf8();
+        Ok(c)
+    }
+
+    /// Reads one of the characters in `options`
+    pub fn one_of(&mut self, options: &str) -> Result<char> {
+        match self.rest().chars().next() {
+            Some(c) if options.contains(c) => {
+                self.pos += c.len_utf8();
+                Ok(c)
+            }
+            _ => {
+                let options = options.chars().map(|c| format!("{c:?}")).collect::<Vec<_>>();
+                Err(self.error(format!("one of {}", options.join(", "))))
+            }
+        }
+    }
+
+    /// Reads everything up to `delim`, then skips past it
+    pub fn until(&mut self, delim: &str) -> Result<&'a str> {
+        let rest = self.rest();
+        let len = rest.find(delim).ok_or_else(|| self.error(format!("text followed by {delim:?}")))?;
+        self.pos += len + delim.len();
+        Ok(&rest[..len])
+    }
+
+    /// Reads one or more items separated by `sep`
+    pub fn list<T>(&mut self, sep: &str, mut item: impl FnMut(&mut Cursor<'a>) -> Result<T>) -> Result<Vec<T>> {
+        let mut items = vec![item(self)?];
+        while self.optional(sep) {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    /// Skips any spaces
+    pub fn skip_spaces(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start_matches(' ').len();
+    }
+
+    /// Checks that the whole line has been parsed
+    pub fn end(&self) -> Result<()> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("end of line"))
+        }
+    }
+}
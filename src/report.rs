@@ -0,0 +1,74 @@
+use std::io::{self, Write};
+
+use serde::Serialize;
+
+use crate::{answer::Answer, answers::Verdict, runner::PartResult, solution::Solution};
+
+/// One part's result, flattened for machine-readable output
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub year: u32,
+    pub day: u32,
+    pub part: u8,
+    /// Left out when the part failed
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    /// Time spent parsing the input, left out for parts that reused what an earlier part parsed
+    pub parse_ns: Option<u64>,
+    pub solve_ns: u64,
+    /// Heap allocations made by the part, when run with `--alloc-stats`
+    pub alloc_count: Option<u64>,
+    pub alloc_bytes: Option<u64>,
+    /// The most heap memory the part had allocated at once
+    pub alloc_peak: Option<u64>,
+    /// [`input_hash`] of the input the part was run on
+    pub input_hash: String,
+    /// `pass`, `fail`, `error`, `timeout` or `limit`, left out when there's no stored answer to compare with
+    pub status: Option<Verdict>,
+}
+
+impl Record {
+    pub fn new(solution: &dyn Solution, result: &PartResult, input_hash: &str, expected: Option<&Answer>) -> Record {
+        let status = match Verdict::check(expected, &result.answer) {
+            Verdict::Missing => None,
+            verdict => Some(verdict),
+        };
+        Record {
+            year: solution.year(),
+            day: solution.day(),
+            part: result.part.number(),
+            answer: result.answer.as_ref().ok().map(|answer| answer.to_string()),
+            error: result.answer.as_ref().err().map(|e| e.to_string()),
+            parse_ns: result.parse.map(|parse| u64::try_from(parse.as_nanos()).unwrap_or(u64::MAX)),
+            solve_ns: u64::try_from(result.elapsed.as_nanos()).unwrap_or(u64::MAX),
+            alloc_count: result.alloc.map(|stats| stats.count),
+            alloc_bytes: result.alloc.map(|stats| stats.bytes),
+            alloc_peak: result.alloc.map(|stats| stats.peak),
+            input_hash: input_hash.to_string(),
+            status,
+        }
+    }
+}
+
+/// A 64-bit FNV-1a hash of the input, as hex, so results can be tied to the input they came from
+pub fn input_hash(input: &str) -> String {
+    let hash = input.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{hash:016x}")
+}
+
+/// Writes the records as a pretty-printed JSON array
+pub fn write_json(records: &[Record], mut writer: impl Write) -> io::Result<()> {
+    serde_json::to_writer_pretty(&mut writer, records)?;
+    writeln!(writer)
+}
+
+/// Writes the records as CSV with a header row
+pub fn write_csv(records: &[Record], writer: impl Write) -> io::Result<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+    for record in records {
+        writer.serialize(record)?;
+    }
+    writer.flush()
+}
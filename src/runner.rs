@@ -0,0 +1,98 @@
+use std::{sync::Arc, time::{Duration, Instant}};
+
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+
+use crate::{alloc::{self, AllocStats}, answer::Answer, error::{AocError, Result}, panics, solution::{Parsed, Part, Solution}, watchdog};
+
+/// The outcome of running one part of a day
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PartResult {
+    pub part: Part,
+    pub answer: Result<Answer>,
+    /// Time spent solving the part, not counting parsing
+    pub elapsed: Duration,
+    /// Time spent parsing the input, on the first part run. The parts after it share what was
+    /// parsed, so they leave this out.
+    #[serde(default)]
+    pub parse: Option<Duration>,
+    /// What the part allocated, when run with allocation tracking on
+    pub alloc: Option<AllocStats>,
+}
+
+/// Runs a solution and times each part
+///
+/// Only `part` is run when it's given, otherwise every part the day has. With a `limit`,
+/// parsing and each part are given up on once they have run that long.
+pub fn solve(solution: &'static dyn Solution, input: &str, part: Option<Part>, limit: Option<Duration>) -> Vec<PartResult> {
+    let mut results = Vec::new();
+    solve_each(solution, input, part, limit, |result| results.push(result));
+    results
+}
+
+/// Like [`solve`], but hands over each part's result as soon as it has one
+pub fn solve_each(solution: &'static dyn Solution, input: &str, part: Option<Part>, limit: Option<Duration>,
+    mut report: impl FnMut(PartResult)) {
+    let (parsed, parse_time) = match limit {
+        Some(limit) => watchdog::parse_within(solution, Arc::from(input), limit),
+        None => parse(solution, input),
+    };
+    let parsed = parsed.map(Arc::new);
+    let mut parse_time = Some(parse_time);
+    for part in selected_parts(solution, part) {
+        let mut result = match (&parsed, limit) {
+            (Ok(parsed), Some(limit)) => watchdog::time_within(solution, part, Arc::clone(parsed), limit),
+            (Ok(parsed), None) => time(solution, part, parsed),
+            // Every part needed what couldn't be parsed, so they all fail the same way
+            (Err(e), _) => PartResult { part, answer: Err(e.clone()), elapsed: Duration::ZERO, parse: None, alloc: None },
+        };
+        result.parse = parse_time.take();
+        report(result);
+    }
+}
+
+/// The parts of `solution` to run, narrowed down to `part` when it's given
+pub fn selected_parts(solution: &dyn Solution, part: Option<Part>) -> Vec<Part> {
+    solution.parts().into_iter().filter(|p| part.is_none_or(|part| part == *p)).collect()
+}
+
+/// Parses and times a day's input, catching the parser if it panics
+pub fn parse(solution: &dyn Solution, input: &str) -> (Result<Parsed>, Duration) {
+    let now = Instant::now();
+    let parsed = panics::catch(solution.day(), || solution.parse(input));
+    (parsed, Instant::now().duration_since(now))
+}
+
+/// Runs and times a single part on what was parsed for it, catching it if it panics
+pub fn time(solution: &dyn Solution, part: Part, parsed: &Parsed) -> PartResult {
+    let now = Instant::now();
+    let (answer, alloc) = alloc::track(|| panics::catch(solution.day(), || solution.solve(part, parsed)));
+    let elapsed = Instant::now().duration_since(now);
+    PartResult { part, answer, elapsed, parse: None, alloc }
+}
+
+/// Runs a solution, printing each answer and how long its part took
+pub fn run(solution: &'static dyn Solution, input: &str, part: Option<Part>) {
+    print(solution, &solve(solution, input, part, None));
+}
+
+/// Prints the answers and timings of a day that has already been solved
+pub fn print(solution: &dyn Solution, results: &[PartResult]) {
+    println!("{}", format!("Day {}:", solution.day()).green());
+    for result in results {
+        if let Some(parse) = result.parse {
+            println!("Parsed in {}", format!("{parse:?}").green());
+        }
+        match &result.answer {
+            Ok(answer) => println!("{answer}"),
+            Err(AocError::Timeout { limit, .. }) => println!("{} after {limit:?}", "TIMEOUT".magenta()),
+            Err(AocError::Panic { message, location, .. }) => println!("{} at {location}: {message}", "FAILED".red()),
+            Err(AocError::LimitExceeded { limit, .. }) => println!("{} ({limit})", "LIMIT".magenta()),
+            Err(e) => println!("{} {e}", "error:".red()),
+        }
+        match result.alloc {
+            Some(stats) => println!("Part {} in {} ({stats})", result.part, format!("{:?}", result.elapsed).green()),
+            None => println!("Part {} in {}", result.part, format!("{:?}", result.elapsed).green()),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use std::{fs, io, path::{Path, PathBuf}};
+
+use crate::{fixtures::Fixture, input::Inputs};
+
+const DAY_TEMPLATE: &str = include_str!("../templates/day.rs.tmpl");
+const FIXTURE_TEMPLATE: &str = include_str!("../templates/fixture.toml.tmpl");
+
+/// Starts a new day from the template and registers it with its year
+///
+/// `root` is the top of the repository, and `inputs` the directory inputs are read from. A year
+/// with no days yet gets its own `aocYYYY` module, added to `lib.rs` and the registry. An empty
+/// input and example fixture are created too, unless they're already there.
+///
+/// Returns the files created or changed.
+pub fn new_day(root: &Path, inputs: &Path, year: u32, day: u32) -> io::Result<Vec<PathBuf>> {
+    let module = root.join(format!("src/aoc{year}/day{day}.rs"));
+    if module.exists() {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} already exists", module.display())));
+    }
+    let mut changed = Vec::new();
+
+    let year_module = root.join(format!("src/aoc{year}.rs"));
+    if !year_module.exists() {
+        let lib = root.join("src/lib.rs");
+        add_year(&lib, year)?;
+        fs::write(&year_module, "crate::days! {\n}\n")?;
+        changed.push(lib);
+    }
+    register_day(&year_module, day)?;
+    changed.push(year_module);
+
+    fs::create_dir_all(module.parent().unwrap())?;
+    fs::write(&module, fill(DAY_TEMPLATE, year, day))?;
+    changed.push(module);
+
+    let input = Inputs::new(inputs).path(year, day);
+    if create_if_missing(&input, "")? {
+        changed.push(input);
+    }
+    let fixture = Fixture::path(year, day);
+    let fixture = root.join(fixture.strip_prefix(".").unwrap_or(&fixture));
+    if create_if_missing(&fixture, &fill(FIXTURE_TEMPLATE, year, day))? {
+        changed.push(fixture);
+    }
+    Ok(changed)
+}
+
+fn fill(template: &str, year: u32, day: u32) -> String {
+    template.replace("{year}", &year.to_string()).replace("{day}", &day.to_string())
+}
+
+fn create_if_missing(path: &Path, contents: &str) -> io::Result<bool> {
+    if path.exists() {
+        return Ok(false);
+    }
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)?;
+    Ok(true)
+}
+
+/// Declares the year's module in `lib.rs` and adds it to `registry`
+fn add_year(lib: &Path, year: u32) -> io::Result<()> {
+    edit_lines(lib, |lines| {
+        let module_year = |line: &str| line.strip_prefix("pub mod aoc")?.strip_suffix(';')?.parse().ok();
+        let registered_year = |line: &str| line.trim().strip_prefix("aoc")?.strip_suffix("::register(&mut registry);")?.parse().ok();
+        if insert_in_order(lines, module_year, year, format!("pub mod aoc{year};"))?
+            && insert_in_order(lines, registered_year, year, format!("    aoc{year}::register(&mut registry);"))? {
+            Ok(())
+        } else {
+            Err(invalid(lib, "couldn't find the other years to add this one next to"))
+        }
+    })
+}
+
+/// Adds the day to the `days!` list in its year's module
+fn register_day(year_module: &Path, day: u32) -> io::Result<()> {
+    edit_lines(year_module, |lines| {
+        let entry_day = |line: &str| line.trim().strip_prefix("day")?.split_once("::")?.0.parse().ok();
+        if insert_in_order(lines, entry_day, day, format!("    day{day}::Day{day},"))? {
+            return Ok(());
+        }
+        // The first day of the year goes in the empty list
+        match lines.iter().rposition(|line| line.trim() == "}") {
+            Some(end) => {
+                lines.insert(end, format!("    day{day}::Day{day},"));
+                Ok(())
+            }
+            None => Err(invalid(year_module, "couldn't find the days! list")),
+        }
+    })
+}
+
+/// Inserts `line` among the lines `key` gets a number from, keeping them in order by it
+///
+/// Returns whether there were any such lines to put it next to.
+fn insert_in_order(lines: &mut Vec<String>, key: impl Fn(&str) -> Option<u32>, value: u32, line: String) -> io::Result<bool> {
+    let keyed = lines.iter().enumerate().filter_map(|(idx, line)| Some((idx, key(line)?))).collect::<Vec<_>>();
+    if keyed.iter().any(|&(_, k)| k == value) {
+        return Err(io::Error::new(io::ErrorKind::AlreadyExists, format!("{} is already there", line.trim())));
+    }
+    let at = match keyed.iter().find(|&&(_, k)| k > value) {
+        Some(&(idx, _)) => idx,
+        None => match keyed.last() {
+            Some(&(idx, _)) => idx + 1,
+            None => return Ok(false),
+        },
+    };
+    lines.insert(at, line);
+    Ok(true)
+}
+
+fn edit_lines(path: &Path, edit: impl FnOnce(&mut Vec<String>) -> io::Result<()>) -> io::Result<()> {
+    let text = fs::read_to_string(path)?;
+    let mut lines = text.lines().map(str::to_string).collect::<Vec<_>>();
+    edit(&mut lines)?;
+    let mut edited = lines.join("\n");
+    if text.ends_with('\n') {
+        edited.push('\n');
+    }
+    fs::write(path, edited)
+}
+
+fn invalid(path: &Path, msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("{}: {msg}", path.display()))
+}
@@ -0,0 +1,173 @@
+use std::{any::Any, fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{answer::Answer, error::Result, params::{self, Overrides}};
+
+/// A single day's puzzle
+///
+/// The input is parsed once into a model that both parts then borrow, so parsing is only paid
+/// for once and can be timed on its own.
+pub trait Solution: Sync {
+    fn year(&self) -> u32;
+    fn day(&self) -> u32;
+    fn parse(&self, input: &str) -> Result<Parsed>;
+    fn part1(&self, parsed: &Parsed) -> Result<Answer>;
+    fn part2(&self, parsed: &Parsed) -> Result<Answer>;
+
+    /// Day 25 only has one part, so it can opt out of the second
+    fn has_part2(&self) -> bool {
+        true
+    }
+
+    fn solve(&self, part: Part, parsed: &Parsed) -> Result<Answer> {
+        match part {
+            Part::One => self.part1(parsed),
+            Part::Two => self.part2(parsed),
+        }
+    }
+
+    /// The day's puzzle parameters with their defaults, for days declaring them with
+    /// [`params!`](crate::params!)
+    fn params(&self) -> Vec<(String, i64)> {
+        Vec::new()
+    }
+
+    /// Solves a part with some of the day's parameters changed from their defaults
+    ///
+    /// Days with parameters override this. The rest only accept no overrides at all.
+    fn solve_with(&self, part: Part, parsed: &Parsed, overrides: &Overrides) -> Result<Answer> {
+        match params::no_params(self.day(), overrides) {
+            Some(e) => Err(e),
+            None => self.solve(part, parsed),
+        }
+    }
+
+    /// The overrides this solution always runs with, for passing on to a child process
+    fn overrides(&self) -> Overrides {
+        Overrides::new()
+    }
+
+    /// The parts this day has, in order
+    fn parts(&self) -> Vec<Part> {
+        if self.has_part2() {
+            vec![Part::One, Part::Two]
+        } else {
+            vec![Part::One]
+        }
+    }
+}
+
+/// A day's input after [`Solution::parse`], holding whatever model the day parses it into
+pub struct Parsed(Box<dyn Any + Send + Sync>);
+
+impl Parsed {
+    pub fn new<T: Any + Send + Sync>(model: T) -> Parsed {
+        Parsed(Box::new(model))
+    }
+
+    /// The model, as the type the day parsed it into
+    ///
+    /// # Panics
+    ///
+    /// Panics if the model isn't a `T`, which means it was parsed by a different day
+    pub fn get<T: Any>(&self) -> &T {
+        match self.0.downcast_ref() {
+            Some(model) => model,
+            None => panic!("the input was parsed into something other than {}", std::any::type_name::<T>()),
+        }
+    }
+}
+
+/// One of the two halves of a day's puzzle
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(&self.number(), f)
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("part must be 1 or 2, not {s}")),
+        }
+    }
+}
+
+/// Every registered solution, ordered by year and day
+#[derive(Default)]
+pub struct Registry {
+    solutions: Vec<&'static dyn Solution>,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry { solutions: Vec::new() }
+    }
+
+    /// Adds a solution, keeping the registry sorted by year and day
+    ///
+    /// # Panics
+    ///
+    /// Panics if a solution for the same year and day is already registered
+    pub fn register(&mut self, solution: &'static dyn Solution) {
+        let key = (solution.year(), solution.day());
+        match self.solutions.binary_search_by_key(&key, |s| (s.year(), s.day())) {
+            Ok(_) => panic!("Day {} of {} registered twice", key.1, key.0),
+            Err(idx) => self.solutions.insert(idx, solution),
+        }
+    }
+
+    pub fn get(&self, year: u32, day: u32) -> Option<&'static dyn Solution> {
+        self.solutions.iter().copied().find(|s| s.year() == year && s.day() == day)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &'static dyn Solution> + '_ {
+        self.solutions.iter().copied()
+    }
+
+    /// All solutions for a single year
+    pub fn year(&self, year: u32) -> impl Iterator<Item = &'static dyn Solution> + '_ {
+        self.iter().filter(move |s| s.year() == year)
+    }
+
+    /// The registered years, in ascending order
+    pub fn years(&self) -> Vec<u32> {
+        let mut years = self.solutions.iter().map(|s| s.year()).collect::<Vec<_>>();
+        years.dedup();
+        years
+    }
+}
+
+/// Declares the day modules of a year and a `register` function adding them to a [`Registry`]
+///
+/// Adding a day is a single `dayN::DayN` line.
+#[macro_export]
+macro_rules! days {
+    ($($module:ident::$solution:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        pub fn register(registry: &mut $crate::solution::Registry) {
+            $(registry.register(&$module::$solution);)*
+        }
+    };
+}
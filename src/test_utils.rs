@@ -7,4 +7,565 @@ fn split_by_big_gap() {
     assert_eq!(utils::split_by_big_gap("1\n    \n2\n   \n3"), vec!["1", "2", "3"]);
     assert_eq!(utils::split_by_big_gap("1\n    \n2\n \n3"), vec!["1", "2", "3"]);
     assert_eq!(utils::split_by_big_gap("1\n2\n \n3"), vec!["1\n2", "3"]);
-}
\ No newline at end of file
+}
+
+#[test]
+fn grids() {
+    use crate::utils::grid::Grid;
+
+    let grid = Grid::parse(8, "30373\n25512\n65332", |c| c.to_digit(10), "a digit").unwrap();
+    assert_eq!((grid.height(), grid.width()), (3, 5));
+    assert_eq!(grid.get((2, 4)), Some(&2));
+    assert_eq!(grid.get((3, 0)), None);
+    assert_eq!(grid.row(1), [2, 5, 5, 1, 2]);
+    assert_eq!(grid.column(3).copied().collect::<Vec<_>>(), [7, 1, 3]);
+    assert_eq!(grid.neighbors8((0, 4)).collect::<Vec<_>>(), [(0, 3), (1, 3), (1, 4)]);
+    assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+    assert_eq!(grid.ray((2, 2), (-1, 1)).collect::<Vec<_>>(), [(1, 3), (0, 4)]);
+    assert_eq!(grid.position(|&height| height == 6), Some((2, 0)));
+    assert_eq!(grid.transposed().row(4), [3, 2, 2]);
+    assert_eq!(grid.map(|&height| if height > 4 { '#' } else { '.' }).to_string(), "...#.\n.##..\n##...");
+
+    let error = Grid::parse(8, "303\n25\n653", |c| c.to_digit(10), "a digit").unwrap_err();
+    assert_eq!(error.to_string(), "day 8, line 2, column 3: expected a digit, found end of line");
+    let error = Grid::parse(8, "303\n2551", |c| c.to_digit(10), "a digit").unwrap_err();
+    assert_eq!(error.to_string(), "day 8, line 2, column 4: expected end of line, as the first row is 3 wide, found '1'");
+
+    let mut rows = Grid::from_ragged_lines(crate::parse::Lines::new(22, "  .#\n.#"), ' ', Some, "a tile").unwrap();
+    assert_eq!(rows.to_string(), "  .#\n.#  ");
+    rows.push_row(['#'; 4]);
+    rows.remove_rows(0..1);
+    assert_eq!(rows.to_string(), ".#  \n####");
+}
+
+#[test]
+fn sparse_grids() {
+    use crate::utils::SparseGrid;
+
+    let mut grid = SparseGrid::new();
+    assert_eq!(grid.bounds(), None);
+    assert_eq!(grid.render(|_| '#'), "");
+    grid.insert((0, 0), 'a');
+    grid.insert((-3, 2), 'b');
+    grid.insert((1, -1), 'c');
+    assert_eq!(grid.bounds(), Some(((-3, -1), (1, 2))));
+    // Looking at empty cells, even far outside the bounds, doesn't add them
+    assert_eq!(grid.get((50, -50)), None);
+    assert!(!grid.contains((-1, 0)));
+    assert_eq!((grid.len(), grid.bounds()), (3, Some(((-3, -1), (1, 2)))));
+
+    assert_eq!(grid.insert((0, 0), 'd'), Some('a'));
+    assert_eq!(grid.remove((-3, 2)), Some('b'));
+    assert_eq!(grid.remove((-3, 2)), None);
+    assert_eq!(grid.bounds(), Some(((0, -1), (1, 0))));
+    assert_eq!(grid.to_string(), ".d\nc.");
+    let mut occupied = grid.iter().map(|(pos, &c)| (pos, c)).collect::<Vec<_>>();
+    occupied.sort();
+    assert_eq!(occupied, [((0, 0), 'd'), ((1, -1), 'c')]);
+}
+
+#[test]
+fn voxel_grids() {
+    use crate::utils::{grid3::{NEIGHBORS26, NEIGHBORS6}, Grid3};
+
+    assert_eq!(NEIGHBORS26.len(), 26);
+    assert!(NEIGHBORS6.iter().all(|step| NEIGHBORS26.contains(step)));
+    assert!(!NEIGHBORS26.contains(&(0, 0, 0)));
+
+    let mut grid = Grid3::new((-2, 0, 5), (1, 2, 6), 0);
+    assert_eq!(grid.bounds(), ((-2, 0, 5), (1, 2, 6)));
+    assert_eq!(grid.positions().count(), 4 * 3 * 2);
+    assert_eq!(grid.get((-3, 0, 5)), None);
+    grid[(1, 2, 6)] = 7;
+    assert_eq!(grid.iter().last(), Some(((1, 2, 6), &7)));
+    assert_eq!(grid.neighbors6((-2, 0, 5)).count(), 3);
+    assert_eq!(grid.neighbors26((-2, 0, 5)).count(), 7);
+
+    // A wall across x = 0, with a gap at y = 2
+    let mut walls = Grid3::new((-2, 0, 0), (2, 2, 0), '.');
+    walls[(0, 0, 0)] = '#';
+    walls[(0, 1, 0)] = '#';
+    let reached = walls.flood_fill([(-2, 0, 0)], |&c| c == '.');
+    assert!(reached[(2, 0, 0)] && !reached[(0, 0, 0)]);
+    walls[(0, 2, 0)] = '#';
+    let reached = walls.flood_fill([(-2, 0, 0), (9, 9, 9), (0, 2, 0)], |&c| c == '.');
+    assert_eq!(reached.iter().filter(|(_, seen)| **seen).count(), 6);
+    assert!(!reached[(1, 0, 0)]);
+}
+
+#[test]
+fn points() {
+    use crate::utils::{Point2, Point3};
+
+    let a = Point2::new(3isize, -4);
+    let b = Point2::new(-1, 2);
+    assert_eq!(a + b, Point2::new(2, -2));
+    assert_eq!(-(a - b), Point2::new(-4, 6));
+    assert_eq!((a.manhattan(b), a.chebyshev(b)), (10, 6));
+    assert_eq!((a - b).signum(), Point2::new(1, -1));
+    assert_eq!(Point2::new(0, 7).signum(), Point2::new(0, 1));
+
+    // Stepping toward a point never overshoots it, and settles on it
+    let mut knot = Point2::new(0, 0);
+    let path = std::iter::from_fn(|| { knot = knot.step_toward(a); Some(knot) }).take(5).collect::<Vec<_>>();
+    assert_eq!(path, [Point2::new(1, -1), Point2::new(2, -2), Point2::new(3, -3), Point2::new(3, -4), Point2::new(3, -4)]);
+
+    let corner = Point2::new(0usize, 5);
+    assert_eq!(corner.offset((-1, 0)), None);
+    assert_eq!(corner.neighbors8().collect::<Vec<_>>(), [Point2::new(0, 4), Point2::new(0, 6), Point2::new(1, 4), Point2::new(1, 5), Point2::new(1, 6)]);
+    assert_eq!(corner.try_cast::<isize>(), Some(Point2::new(0, 5)));
+    assert_eq!(Point2::new(2isize, -1).try_cast::<usize>(), None);
+    assert_eq!(<(usize, usize)>::from(corner), (0, 5));
+
+    let mut c = Point3::new(1, 2, 3);
+    c += Point3::new(1, 1, 1) * 2;
+    assert_eq!(c, Point3::new(3, 4, 5));
+    assert_eq!((c.manhattan(Point3::default()), c.chebyshev(Point3::default())), (12, 5));
+    assert_eq!(Point3::new(0u32, 0, 0).neighbors6().count(), 3);
+    assert_eq!(Point3::from((1, 1, 1)).neighbors26().count(), 26);
+}
+
+#[test]
+fn directions() {
+    use crate::utils::{Direction, Direction8};
+
+    for (chars, dir) in ["^UN", ">RE", "vDS", "<LW"].into_iter().zip(Direction::ALL) {
+        assert!(chars.chars().all(|c| Direction::from_char(c) == Some(dir)));
+        assert_eq!(Direction::from_char(dir.arrow()), Some(dir));
+        assert_eq!(dir.turn_left().turn_right(), dir);
+        assert_eq!(dir.turn_right().turn_right(), dir.turn_around());
+        let (row, col) = dir.offset();
+        assert_eq!(dir.turn_around().offset(), (-row, -col));
+        assert_eq!(dir.is_horizontal(), row == 0);
+    }
+    assert_eq!(Direction::from_char('x'), None);
+
+    // Stepping off any side of the box comes back in on the other
+    let (min, max) = ((1, 1), (3, 5));
+    assert_eq!(Direction::Up.step_wrapping((1, 2), min, max), (3, 2));
+    assert_eq!(Direction::Down.step_wrapping((3, 2), min, max), (1, 2));
+    assert_eq!(Direction::Right.step_wrapping((2, 5), min, max), (2, 1));
+    assert_eq!(Direction::Right.step_wrapping((2, 3), min, max), (2, 4));
+
+    let offsets = Direction8::ALL.map(|dir| dir.offset());
+    assert_eq!(offsets, [(-1, 0), (-1, 1), (0, 1), (1, 1), (1, 0), (1, -1), (0, -1), (-1, -1)]);
+    assert_eq!(Direction8::NorthWest.turn_right(), Direction8::North);
+    assert_eq!(Direction8::North.turn_left(), Direction8::NorthWest);
+    assert_eq!(Direction8::SouthEast.turn_around(), Direction8::NorthWest);
+    assert_eq!(Direction8::from_char('<'), Some(Direction8::West));
+}
+
+#[test]
+fn registry_has_every_2022_day_in_order() {
+    let registry = crate::registry();
+    let days = registry.year(2022).map(|s| s.day()).collect::<Vec<_>>();
+    assert_eq!(days, (1..=25).collect::<Vec<_>>());
+    assert_eq!(registry.years(), vec![2022]);
+    assert!(registry.get(2022, 16).is_some());
+    assert!(registry.get(2022, 26).is_none());
+}
+
+#[test]
+fn answer_display() {
+    use crate::answer::Answer;
+    assert_eq!(Answer::from(24000_u32).to_string(), "24000");
+    assert_eq!(Answer::from("CMZ").to_string(), "CMZ");
+    assert_eq!(Answer::grid(["#..", ".#."]).to_string(), "#..\n.#.");
+}
+
+#[test]
+fn day_range_parsing() {
+    use crate::cli::DayRange;
+    assert_eq!("16".parse(), Ok(DayRange { first: 16, last: 16 }));
+    assert_eq!("10-15".parse(), Ok(DayRange { first: 10, last: 15 }));
+    assert!("15-10".parse::<DayRange>().is_err());
+    assert!("0".parse::<DayRange>().is_err());
+    assert!("26".parse::<DayRange>().is_err());
+    assert!("day".parse::<DayRange>().is_err());
+}
+
+#[test]
+fn verdicts_compare_answer_text() {
+    use crate::{answer::Answer, answers::Verdict};
+    use crate::error::AocError;
+    assert_eq!(Verdict::check(Some(&Answer::Str("24000".into())), &Ok(Answer::Int(24000))), Verdict::Pass);
+    assert_eq!(Verdict::check(Some(&Answer::Int(24001)), &Ok(Answer::Int(24000))), Verdict::Mismatch);
+    assert_eq!(Verdict::check(None, &Ok(Answer::Int(24000))), Verdict::Missing);
+    assert_eq!(Verdict::check(None, &Err(AocError::no_answer(1, "no elves"))), Verdict::Failed);
+}
+
+#[test]
+fn bench_stats() {
+    use crate::bench::Stats;
+    use std::time::Duration;
+
+    let timings = [5, 1, 3, 2, 4].map(Duration::from_millis);
+    let stats = Stats::new(&timings).unwrap();
+    assert_eq!(stats.iterations, 5);
+    assert_eq!(stats.min, Duration::from_millis(1));
+    assert_eq!(stats.median, Duration::from_millis(3));
+    assert_eq!(stats.mean, Duration::from_millis(3));
+    assert_eq!(stats.p95, Duration::from_millis(5));
+    assert_eq!(stats.max, Duration::from_millis(5));
+
+    let even = Stats::new(&[1, 2, 3, 4].map(Duration::from_millis)).unwrap();
+    assert_eq!(even.median, Duration::from_micros(2500));
+    assert_eq!(Stats::new(&[]), None);
+}
+
+#[test]
+fn report_records() {
+    use crate::{answer::Answer, aoc2022::day1::Day1, report::{input_hash, Record}, runner::PartResult, solution::Part};
+    use std::time::Duration;
+
+    // FNV-1a test vectors
+    assert_eq!(input_hash(""), "cbf29ce484222325");
+    assert_eq!(input_hash("a"), "af63dc4c8601ec8c");
+
+    let result = PartResult { part: Part::Two, answer: Ok(Answer::Int(45000)), elapsed: Duration::from_micros(3), parse: None, alloc: None };
+    let record = Record::new(&Day1, &result, "abc", Some(&Answer::Int(45000)));
+    assert_eq!((record.day, record.part, record.solve_ns), (1, 2, 3000));
+    assert_eq!(serde_json::to_value(&record).unwrap()["status"], "pass");
+    let record = Record::new(&Day1, &result, "abc", Some(&Answer::Int(1)));
+    assert_eq!(serde_json::to_value(&record).unwrap()["status"], "fail");
+    assert_eq!(Record::new(&Day1, &result, "abc", None).status, None);
+}
+
+#[test]
+fn input_paths() {
+    use crate::{cli::InputOverride, input::{InputError, Inputs}};
+    use std::path::PathBuf;
+
+    let inputs = Inputs::new("/aoc").with_override(16, "-");
+    assert_eq!(inputs.path(2022, 1), PathBuf::from("/aoc/2022/day1.txt"));
+    assert_eq!(inputs.path(2022, 16), PathBuf::from("-"));
+
+    let missing = Inputs::new("/nonexistent").read(2022, 3).unwrap_err();
+    assert!(matches!(missing, InputError::Missing { day: 3, .. }));
+    assert_eq!(missing.to_string(), "day 3 has no input at /nonexistent/2022/day3.txt");
+
+    assert_eq!("16=ex.txt".parse(), Ok(InputOverride { day: Some(16), path: "ex.txt".into() }));
+    assert_eq!("a=b.txt".parse(), Ok(InputOverride { day: None, path: "a=b.txt".into() }));
+    assert!("26=ex.txt".parse::<InputOverride>().is_err());
+}
+
+#[test]
+fn inputs_ending_in_a_newline() {
+    use crate::{answer::Answer, aoc2022::day1, input::{trim_newline, Inputs}};
+
+    assert_eq!(trim_newline("1\n2\n".to_string()), "1\n2");
+    assert_eq!(trim_newline("1\r\n2\r\n".to_string()), "1\r\n2");
+    assert_eq!(trim_newline("1\n\n".to_string()), "1\n");
+    assert_eq!(trim_newline("1".to_string()), "1");
+
+    let dir = std::env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("2022")).unwrap();
+    std::fs::write(dir.join("2022/day1.txt"), "1000\n2000\n\n3000\n\n500\n").unwrap();
+    let input = Inputs::new(&dir).read(2022, 1);
+    std::fs::remove_dir_all(&dir).unwrap();
+    let elves = day1::parse(&input.unwrap()).unwrap();
+    assert_eq!(day1::part2(&elves), Ok(Answer::Int(6500)));
+}
+
+#[test]
+fn parse_errors_point_at_the_input() {
+    use crate::aoc2022::{day11, day13, day15, day20};
+
+    let error = day15::parse("Sensor at x=2, y=18: closest beacon is at x=-2, y=15\nSensor at x=9 y=16")
+        .unwrap_err();
+    assert_eq!(error.to_string(), r#"day 15, line 2, column 14: expected ", y=", found " y=16""#);
+
+    let error = day13::parse("[1,[2]]\n[1,2,]").unwrap_err();
+    assert_eq!(error.to_string(), r#"day 13, line 2, column 6: expected a number or a list, found "]""#);
+    let error = day13::parse("[1]\n[2]\n[3]").unwrap_err();
+    assert_eq!(error.to_string(), r#"day 13, line 3, column 1: expected a blank line, found '['"#);
+
+    let error = day11::parse("Monkey 0:\n  Starting items: 79, 98").err().unwrap();
+    assert_eq!(error.to_string(), "day 11, line 3, column 1: expected the operation, found end of block");
+
+    let error = day20::parse("1\n2\n-3").and_then(|coords| day20::part1(&coords)).unwrap_err();
+    assert_eq!(error.to_string(), "day 20: there is no 0 in the file");
+}
+
+#[test]
+fn parsing_is_timed_once() {
+    use crate::{answer::Answer, aoc2022::day1::Day1, runner};
+
+    for limit in [None, Some(std::time::Duration::from_secs(5))] {
+        let results = runner::solve(&Day1, "1000\n2000\n\n4000", None, limit);
+        assert_eq!(results[0].answer, Ok(Answer::Int(4000)));
+        assert!(results[0].parse.is_some());
+        // Part 2 borrows what part 1 parsed
+        assert_eq!(results[1].parse, None);
+
+        let results = runner::solve(&Day1, "1000\nlots", None, limit);
+        assert!(results.iter().all(|result| result.answer.as_ref().unwrap_err().to_string().starts_with("day 1, line 2")));
+    }
+}
+
+#[test]
+fn parts_time_out() {
+    use crate::{answer::Answer, answers::Verdict, cli::TimeoutOverride, error::Result, runner, solution::{Parsed, Solution},
+        watchdog::Timeouts};
+    use std::time::Duration;
+
+    struct Slow;
+    impl Solution for Slow {
+        fn year(&self) -> u32 { 2022 }
+        fn day(&self) -> u32 { 1 }
+        fn parse(&self, _input: &str) -> Result<Parsed> { Ok(Parsed::new(())) }
+        fn part1(&self, _parsed: &Parsed) -> Result<Answer> { Ok(Answer::Int(1)) }
+        fn part2(&self, _parsed: &Parsed) -> Result<Answer> {
+            std::thread::sleep(Duration::from_secs(5));
+            Ok(Answer::Int(2))
+        }
+    }
+
+    let results = runner::solve(&Slow, "", None, Some(Duration::from_millis(50)));
+    assert_eq!(results[0].answer, Ok(Answer::Int(1)));
+    assert_eq!(Verdict::check(Some(&Answer::Int(2)), &results[1].answer), Verdict::Timeout);
+    assert_eq!(results[1].answer.as_ref().unwrap_err().to_string(), "day 1: gave up after 50ms");
+
+    let timeouts = Timeouts::new(Some(Duration::from_secs(30))).with_override(16, Duration::from_secs(120));
+    assert_eq!(timeouts.limit(16), Some(Duration::from_secs(120)));
+    assert_eq!(timeouts.limit(15), Some(Duration::from_secs(30)));
+    assert_eq!(Timeouts::default().limit(15), None);
+
+    assert_eq!("16=2m".parse(), Ok(TimeoutOverride { day: Some(16), limit: Duration::from_secs(120) }));
+    assert_eq!("500ms".parse(), Ok(TimeoutOverride { day: None, limit: Duration::from_millis(500) }));
+    assert_eq!("1.5".parse(), Ok(TimeoutOverride { day: None, limit: Duration::from_millis(1500) }));
+    assert!("16=soon".parse::<TimeoutOverride>().is_err());
+}
+
+#[test]
+fn panics_are_caught() {
+    use crate::{answer::Answer, answers::Verdict, error::{AocError, Result}, runner, solution::{Parsed, Solution}};
+    use std::time::Duration;
+
+    struct Buggy;
+    impl Solution for Buggy {
+        fn year(&self) -> u32 { 2022 }
+        fn day(&self) -> u32 { 3 }
+        fn parse(&self, input: &str) -> Result<Parsed> { Ok(Parsed::new(input.to_string())) }
+        fn part1(&self, parsed: &Parsed) -> Result<Answer> { Ok(Answer::Int(parsed.get::<String>().parse::<i128>().unwrap())) }
+        fn part2(&self, _parsed: &Parsed) -> Result<Answer> { Ok(Answer::Int(2)) }
+    }
+
+    for limit in [None, Some(Duration::from_secs(5))] {
+        let results = runner::solve(&Buggy, "x", None, limit);
+        let Err(AocError::Panic { day: 3, message, location }) = &results[0].answer else {
+            panic!("expected a panic, got {:?}", results[0].answer);
+        };
+        assert!(message.starts_with("called `Result::unwrap()` on an `Err` value"), "{message}");
+        assert!(location.starts_with("src/test_utils.rs:"), "{location}");
+        assert_eq!(Verdict::check(None, &results[0].answer), Verdict::Failed);
+        assert_eq!(results[1].answer, Ok(Answer::Int(2)));
+    }
+}
+
+#[test]
+fn isolation_args() {
+    use crate::cli::{Cli, Command};
+    use clap::Parser;
+
+    let cli = Cli::try_parse_from(["aoc", "run", "2022", "--isolate", "--memory-limit", "512M", "--cpu-limit", "30"]).unwrap();
+    let Some(Command::Run(args)) = cli.command else { panic!("expected a run command") };
+    assert!(args.isolate.isolate);
+    assert_eq!(args.isolate.memory_limit, Some(512 * 1024 * 1024));
+    assert_eq!(args.isolate.cpu_limit, Some(30));
+
+    assert!(Cli::try_parse_from(["aoc", "run", "2022", "--memory-limit", "4G"]).is_err());
+    assert!(Cli::try_parse_from(["aoc", "run", "2022", "--isolate", "--memory-limit", "lots"]).is_err());
+}
+
+#[test]
+fn scaffolding_days() {
+    use crate::scaffold;
+    use std::{fs, io::ErrorKind};
+
+    let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+    let inputs = root.join("inputs");
+    fs::create_dir_all(root.join("src")).unwrap();
+    fs::write(root.join("src/lib.rs"), "pub mod aoc2022;\npub mod utils;\n\nfn registry() {\n    aoc2022::register(&mut registry);\n}\n").unwrap();
+    fs::write(root.join("src/aoc2022.rs"), "crate::days! {\n    day1::Day1,\n    day3::Day3,\n}\n").unwrap();
+
+    scaffold::new_day(&root, &inputs, 2022, 2).unwrap();
+    assert_eq!(fs::read_to_string(root.join("src/aoc2022.rs")).unwrap(), "crate::days! {\n    day1::Day1,\n    day2::Day2,\n    day3::Day3,\n}\n");
+    assert!(fs::read_to_string(root.join("src/aoc2022/day2.rs")).unwrap().contains("impl Solution for Day2"));
+    assert_eq!(fs::read_to_string(inputs.join("2022/day2.txt")).unwrap(), "");
+    assert!(root.join("fixtures/2022/day2.toml").exists());
+
+    scaffold::new_day(&root, &inputs, 2021, 25).unwrap();
+    assert_eq!(fs::read_to_string(root.join("src/lib.rs")).unwrap(),
+        "pub mod aoc2021;\npub mod aoc2022;\npub mod utils;\n\nfn registry() {\n    aoc2021::register(&mut registry);\n    aoc2022::register(&mut registry);\n}\n");
+    assert_eq!(fs::read_to_string(root.join("src/aoc2021.rs")).unwrap(), "crate::days! {\n    day25::Day25,\n}\n");
+
+    assert_eq!(scaffold::new_day(&root, &inputs, 2022, 3).unwrap_err().kind(), ErrorKind::AlreadyExists);
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn comparing_benchmarks() {
+    use crate::{aoc2022::{day1::Day1, day20::Day20}, bench::Stats, history::{self, Context, Entry, Machine}, params::{Overrides, Tuned}, solution::Part};
+    use std::time::Duration;
+
+    let machine = Machine { host: String::from("box"), cpu: None, cores: 4, os: String::from("linux"), arch: String::from("x86_64") };
+    let context = |run, commit: &str| Context {
+        run, commit: Some(commit.to_string()), dirty: false, rustc: String::from("rustc 1.70.0"), machine: machine.clone(),
+    };
+    let entry = |context: &Context, part, millis| {
+        let stats = Stats::new(&[Duration::from_millis(millis)]).unwrap();
+        Entry::new(context, &Day1, part, &stats)
+    };
+    let (old, new, newer) = (context(1, "aaaa111"), context(2, "bbbb222"), context(3, "cccc333"));
+    let entries = vec![
+        entry(&old, Part::One, 10), entry(&old, Part::Two, 20),
+        entry(&new, Part::One, 12), entry(&new, Part::Two, 10),
+        entry(&newer, Part::One, 100),
+    ];
+
+    assert_eq!(history::find_run(&entries, None, None), Some(&newer));
+    assert_eq!(history::find_run(&entries, None, Some(3)), Some(&new));
+    assert_eq!(history::find_run(&entries, Some("aaaa"), None), Some(&old));
+    assert_eq!(history::find_run(&entries, Some("dddd"), None), None);
+
+    let changes = history::compare(&entries, old.run, new.run);
+    assert_eq!(changes.len(), 2);
+    assert!((changes[0].percent() - 20.0).abs() < 1e-9);
+    assert!(changes[0].is_regression(10.0) && !changes[0].is_regression(25.0));
+    assert!((changes[1].percent() + 50.0).abs() < 1e-9);
+    assert!(!changes[1].is_regression(0.0));
+    // Parts only one of the runs benchmarked are left out
+    assert_eq!(history::compare(&entries, old.run, newer.run).len(), 1);
+
+    // So are parts benchmarked with different params, which are really different puzzles
+    let tuned = Tuned::leak(&Day20, Overrides::from([(String::from("KEY"), 1)]));
+    let stats = Stats::new(&[Duration::from_millis(5)]).unwrap();
+    let (plain, keyed) = (context(4, "dddd444"), context(5, "eeee555"));
+    let entries = vec![Entry::new(&plain, &Day20, Part::Two, &stats), Entry::new(&keyed, tuned, Part::Two, &stats)];
+    assert!(history::compare(&entries, plain.run, keyed.run).is_empty());
+    let line = serde_json::to_string(&entries[1]).unwrap();
+    assert!(line.contains(r#""params":{"KEY":1}"#), "{line}");
+    assert_eq!(serde_json::from_str::<Entry>(&line).unwrap(), entries[1]);
+    assert!(!serde_json::to_string(&entries[0]).unwrap().contains("params"));
+}
+
+#[test]
+fn dashboard_lists_every_day() {
+    use crate::{dashboard::Dashboard, input::Inputs, watchdog::Timeouts};
+    use ratatui::{backend::TestBackend, Terminal};
+
+    let mut dashboard = Dashboard::new(Inputs::default(), Timeouts::default()).unwrap();
+    let mut terminal = Terminal::new(TestBackend::new(100, 32)).unwrap();
+    terminal.draw(|frame| dashboard.draw(frame)).unwrap();
+    let screen = terminal.backend().buffer().content().iter().map(|cell| cell.symbol()).collect::<String>();
+    let lines = screen.chars().collect::<Vec<_>>().chunks(100).map(|line| line.iter().collect::<String>()).collect::<Vec<_>>();
+
+    assert!(lines[0].contains("Advent of Code, real input"), "{}", lines[0]);
+    assert!(lines[1].contains("Year") && lines[1].contains("Stars") && lines[1].contains("Part 2"), "{}", lines[1]);
+    // Every 2022 day has both its answers stored, apart from day 25 which only has one part
+    assert!(lines[2].starts_with("│2022  1    ★★"), "{}", lines[2]);
+    assert!(lines.iter().any(|line| line.starts_with("│2022  25   ★ ")));
+    assert!(lines[31].contains("q quit"));
+}
+
+#[test]
+fn overriding_params() {
+    use crate::{aoc2022::{day1::Day1, day15::Day15, day16::Day16, day20::Day20}, cli::ParamOverride, error::AocError, fixtures::Fixture,
+        params::{Overrides, Tuned}, solution::{Part, Solution}};
+
+    let param = "15:column_at=10".parse::<ParamOverride>().unwrap();
+    assert_eq!(param, ParamOverride { day: Some(15), name: String::from("COLUMN_AT"), value: 10 });
+    assert_eq!("RANGE=20".parse::<ParamOverride>().unwrap().day, None);
+    assert!("RANGE".parse::<ParamOverride>().is_err());
+    assert!("RANGE=twenty".parse::<ParamOverride>().is_err());
+
+    assert_eq!(Day15.params(), [(String::from("COLUMN_AT"), 2000000), (String::from("RANGE"), 4000000)]);
+    assert!(Day1.params().is_empty());
+
+    let unknown = Overrides::from([(String::from("KEY"), 1)]);
+    let parsed = Day1.parse("1\n").unwrap();
+    assert!(matches!(Day1.solve_with(Part::One, &parsed, &unknown), Err(AocError::InvalidParam { day: 1, .. })));
+    let too_long = Overrides::from([(String::from("P1_TIME_MAX"), 300)]);
+    let parsed = Day16.parse(&Fixture::load(2022, 16).unwrap().input).unwrap();
+    assert!(matches!(Day16.solve_with(Part::One, &parsed, &too_long), Err(AocError::InvalidParam { day: 16, .. })));
+    let no_time = Overrides::from([(String::from("P1_TIME_MAX"), 0)]);
+    let error = Day16.solve_with(Part::One, &parsed, &no_time).unwrap_err();
+    assert_eq!(error.to_string(), "day 16: parameter P1_TIME_MAX has to be in 1..=30");
+
+    // With a key of 1, part 2 only differs from part 1 by mixing ten times instead of once
+    let input = "1\n2\n-3\n3\n-2\n0\n4";
+    let tuned = Tuned::leak(&Day20, unknown);
+    assert_eq!(tuned.overrides().get("KEY"), Some(&1));
+    assert_eq!(tuned.part2(&tuned.parse(input).unwrap()).unwrap().to_string(), "2");
+
+    // Tuning a day the same way again reuses the first one instead of leaking another
+    let again = Tuned::leak(&Day20, Overrides::from([(String::from("KEY"), 1)]));
+    assert!(std::ptr::addr_eq(tuned, again));
+    let other = Tuned::leak(&Day15, Overrides::from([(String::from("KEY"), 1)]));
+    assert_eq!(other.day(), 15);
+}
+
+/// Checks one part of a 2022 day against the answer given for its example in `fixtures/2022`,
+/// if there is one, with any parameters the fixture sets
+fn check_example(day: u32, part: crate::solution::Part) {
+    let fixture = crate::fixtures::Fixture::load(2022, day).unwrap();
+    let solution = crate::registry().get(2022, day).unwrap();
+    if let Some(expected) = fixture.expected.get(&part) {
+        let actual = solution.parse(&fixture.input)
+            .and_then(|parsed| solution.solve_with(part, &parsed, &fixture.params))
+            .unwrap_or_else(|e| panic!("{e}"));
+        assert_eq!(actual.to_string(), expected.to_string(), "day {day} part {part} example");
+    }
+}
+
+macro_rules! example_tests {
+    ($($test:ident: $day:literal),* $(,)?) => {
+        $(
+            #[test]
+            fn $test() {
+                use crate::solution::Part;
+                check_example($day, Part::One);
+                check_example($day, Part::Two);
+            }
+        )*
+    };
+}
+
+example_tests! {
+    day1_example: 1,
+    day2_example: 2,
+    day3_example: 3,
+    day4_example: 4,
+    day5_example: 5,
+    day6_example: 6,
+    day7_example: 7,
+    day8_example: 8,
+    day9_example: 9,
+    day10_example: 10,
+    day11_example: 11,
+    day12_example: 12,
+    day13_example: 13,
+    day14_example: 14,
+    day15_example: 15,
+    day16_example: 16,
+    day17_example: 17,
+    day18_example: 18,
+    day20_example: 20,
+    day21_example: 21,
+    day22_example: 22,
+    day23_example: 23,
+    day24_example: 24,
+    day25_example: 25,
+}
+
+#[test]
+fn day19_example_part1() {
+    check_example(19, crate::solution::Part::One);
+}
+
+#[test]
+#[ignore = "takes several minutes even in release builds"]
+fn day19_example_part2() {
+    check_example(19, crate::solution::Part::Two);
+}
@@ -1,3 +1,15 @@
+pub mod direction;
+pub mod grid;
+pub mod grid3;
+pub mod point;
+pub mod sparse_grid;
+
+pub use direction::{Direction, Direction8};
+pub use grid::Grid;
+pub use grid3::Grid3;
+pub use point::{Point2, Point3};
+pub use sparse_grid::SparseGrid;
+
 /// Returns a vector of strings, split by more than one newline
 /// 
 /// # Arguments
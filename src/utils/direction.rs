@@ -0,0 +1,154 @@
+//! Which way something faces or moves on a grid, and how it turns
+//!
+//! Offsets are `(row, column)` steps like a [`Grid`](super::Grid)'s, so up is toward row 0
+//! and `Grid::step(pos, dir.offset())` moves a position one cell along.
+//!
+//! ```
+//! use aoc::utils::direction::{Direction, Direction8};
+//!
+//! let dir = Direction::from_char('^').unwrap();
+//! assert_eq!(dir, Direction::from_char('N').unwrap());
+//! assert_eq!(dir.turn_right(), Direction::Right);
+//! assert_eq!(dir.turn_around().offset(), (1, 0));
+//! assert_eq!(Direction::Left.step_wrapping((2, 1), (1, 1), (3, 4)), (2, 4));
+//! assert_eq!(Direction8::from(dir).turn_right(), Direction8::NorthEast);
+//! ```
+
+use super::grid::Pos;
+
+/// One of the four ways along a grid's rows and columns, listed clockwise from up
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Direction::Up, Direction::Right, Direction::Down, Direction::Left];
+
+    /// Reads an arrow (`^v<>`), a letter for up, down, left or right (`UDLR`), or a compass
+    /// point (`NSEW`) with north as up
+    pub fn from_char(c: char) -> Option<Direction> {
+        match c {
+            '^' | 'U' | 'N' => Some(Direction::Up),
+            '>' | 'R' | 'E' => Some(Direction::Right),
+            'v' | 'D' | 'S' => Some(Direction::Down),
+            '<' | 'L' | 'W' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    /// The arrow pointing this way
+    pub fn arrow(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::Right => '>',
+            Direction::Down => 'v',
+            Direction::Left => '<',
+        }
+    }
+
+    /// The `(row, column)` step one cell this way
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Right => (0, 1),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+        }
+    }
+
+    pub fn turn_left(self) -> Direction {
+        Direction::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn turn_right(self) -> Direction {
+        Direction::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_around(self) -> Direction {
+        Direction::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Whether this way runs along a row, rather than up or down a column
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+
+    /// The position one cell this way from `pos`, coming back in on the other side of the
+    /// rectangle from `min` to `max`, both included, if it steps out of it
+    pub fn step_wrapping(self, pos: Pos, min: Pos, max: Pos) -> Pos {
+        let wrap = |n: usize, step: isize, lo: usize, hi: usize| {
+            let len = (hi - lo + 1) as isize;
+            lo + (n as isize - lo as isize + step).rem_euclid(len) as usize
+        };
+        let (row, col) = self.offset();
+        (wrap(pos.0, row, min.0, max.0), wrap(pos.1, col, min.1, max.1))
+    }
+}
+
+/// One of the eight ways to a touching cell, diagonals included, listed clockwise from north
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North, Direction8::NorthEast, Direction8::East, Direction8::SouthEast,
+        Direction8::South, Direction8::SouthWest, Direction8::West, Direction8::NorthWest,
+    ];
+
+    /// Reads one of the straight directions [`Direction::from_char`] does
+    pub fn from_char(c: char) -> Option<Direction8> {
+        Direction::from_char(c).map(Direction8::from)
+    }
+
+    /// The `(row, column)` step one cell this way, with north as up
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction8::North => (-1, 0),
+            Direction8::NorthEast => (-1, 1),
+            Direction8::East => (0, 1),
+            Direction8::SouthEast => (1, 1),
+            Direction8::South => (1, 0),
+            Direction8::SouthWest => (1, -1),
+            Direction8::West => (0, -1),
+            Direction8::NorthWest => (-1, -1),
+        }
+    }
+
+    /// An eighth of a turn anticlockwise
+    pub fn turn_left(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 7) % 8]
+    }
+
+    /// An eighth of a turn clockwise
+    pub fn turn_right(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 1) % 8]
+    }
+
+    pub fn turn_around(self) -> Direction8 {
+        Direction8::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(dir: Direction) -> Direction8 {
+        match dir {
+            Direction::Up => Direction8::North,
+            Direction::Right => Direction8::East,
+            Direction::Down => Direction8::South,
+            Direction::Left => Direction8::West,
+        }
+    }
+}
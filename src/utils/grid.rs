@@ -0,0 +1,244 @@
+//! A rectangular grid of cells, for puzzles drawn as a map of characters
+//!
+//! Positions are `(row, column)` pairs counting from the top left corner.
+//!
+//! ```
+//! use aoc::utils::grid::Grid;
+//!
+//! let grid = Grid::parse(8, "303\n255\n653", |c| c.to_digit(10), "a digit")?;
+//! assert_eq!(grid[(1, 2)], 5);
+//! assert_eq!(grid.ray((1, 1), (0, 1)).map(|pos| grid[pos]).collect::<Vec<_>>(), [5]);
+//! assert_eq!(grid.neighbors4((0, 0)).collect::<Vec<_>>(), [(0, 1), (1, 0)]);
+//! assert_eq!(grid.transposed().to_string(), "326\n055\n353");
+//!
+//! let error = Grid::parse(8, "303\n2x5", |c| c.to_digit(10), "a digit").unwrap_err();
+//! assert_eq!(error.to_string(), "day 8, line 2, column 2: expected a digit, found 'x'");
+//! # Ok::<(), aoc::error::AocError>(())
+//! ```
+
+use std::{fmt::{self, Display}, ops::{Index, IndexMut, Range}};
+
+use crate::{error::Result, parse::{Line, Lines}};
+
+/// A cell's position, as its row then its column
+pub type Pos = (usize, usize);
+
+/// The steps to the four cells sharing a side with another: up, left, right and down
+pub const NEIGHBORS4: [(isize, isize); 4] = [(-1, 0), (0, -1), (0, 1), (1, 0)];
+
+/// The steps to the eight cells touching another, diagonals included, in reading order
+pub const NEIGHBORS8: [(isize, isize); 8] = [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    height: usize,
+    width: usize,
+}
+
+impl<T> Grid<T> {
+    /// A grid with every cell set to `fill`
+    pub fn new(height: usize, width: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid { cells: vec![fill; height * width], height, width }
+    }
+
+    /// A grid with each cell set to what `f` gives for its position
+    pub fn from_fn(height: usize, width: usize, mut f: impl FnMut(Pos) -> T) -> Grid<T> {
+        let cells = (0..height).flat_map(|row| (0..width).map(move |col| (row, col))).map(&mut f).collect();
+        Grid { cells, height, width }
+    }
+
+    /// Reads a grid with one line per row and one character per cell, which `cell` turns
+    /// into the cell's value, or `None` for a character that shouldn't be there
+    ///
+    /// Every row has to be as wide as the first.
+    pub fn parse(day: u32, input: &str, cell: impl FnMut(char) -> Option<T>, expected: impl Display) -> Result<Grid<T>> {
+        Grid::from_lines(Lines::new(day, input), cell, expected)
+    }
+
+    /// [`Grid::parse`] for a grid making up only some of the input's lines
+    pub fn from_lines<'a>(lines: impl IntoIterator<Item = Line<'a>>, mut cell: impl FnMut(char) -> Option<T>,
+        expected: impl Display) -> Result<Grid<T>> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for line in lines {
+            let start = cells.len();
+            for (column, c) in line.columns() {
+                if let Some(width) = width.filter(|&width| column > width) {
+                    return Err(line.error_at(column, format!("end of line, as the first row is {width} wide")));
+                }
+                cells.push(cell(c).ok_or_else(|| line.error_at(column, &expected))?);
+            }
+            let len = cells.len() - start;
+            if len == 0 || width.is_some_and(|width| len < width) {
+                return Err(line.error_at(len + 1, &expected));
+            }
+            width = Some(len);
+            height += 1;
+        }
+        Ok(Grid { cells, height, width: width.unwrap_or(0) })
+    }
+
+    /// Like [`Grid::from_lines`], but rows can have any width, with the short ones filled out
+    /// to the widest with `fill`
+    pub fn from_ragged_lines<'a>(lines: impl IntoIterator<Item = Line<'a>>, fill: T, mut cell: impl FnMut(char) -> Option<T>,
+        expected: impl Display) -> Result<Grid<T>>
+    where
+        T: Clone,
+    {
+        let mut rows = Vec::new();
+        for line in lines {
+            let row = line.columns()
+                .map(|(column, c)| cell(c).ok_or_else(|| line.error_at(column, &expected)))
+                .collect::<Result<Vec<_>>>()?;
+            rows.push(row);
+        }
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(height * width);
+        for mut row in rows {
+            row.resize(width, fill.clone());
+            cells.extend(row);
+        }
+        Ok(Grid { cells, height, width })
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos).then(|| &self.cells[pos.0 * self.width + pos.1])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.contains(pos).then(|| &mut self.cells[pos.0 * self.width + pos.1])
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.0 < self.height && pos.1 < self.width
+    }
+
+    /// The position `step` away from `pos`, if it's still inside the grid
+    pub fn step(&self, pos: Pos, step: (isize, isize)) -> Option<Pos> {
+        let moved = (pos.0.checked_add_signed(step.0)?, pos.1.checked_add_signed(step.1)?);
+        self.contains(moved).then_some(moved)
+    }
+
+    /// The positions sharing a side with `pos`, leaving out any off the edge
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS4.into_iter().filter_map(move |step| self.step(pos, step))
+    }
+
+    /// The positions touching `pos`, diagonals included, leaving out any off the edge
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS8.into_iter().filter_map(move |step| self.step(pos, step))
+    }
+
+    /// The positions reached by stepping from `pos` again and again until the edge, not
+    /// counting `pos` itself
+    pub fn ray(&self, pos: Pos, step: (isize, isize)) -> impl Iterator<Item = Pos> + '_ {
+        std::iter::successors(self.step(pos, step), move |&pos| self.step(pos, step))
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(col < self.width, "column {col} is outside a grid {} wide", self.width);
+        self.cells.iter().skip(col).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    /// Adds a row after the last one, which has to be as wide as the others
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = T>) {
+        let start = self.cells.len();
+        self.cells.extend(row);
+        assert_eq!(self.cells.len() - start, self.width, "the new row isn't as wide as the grid");
+        self.height += 1;
+    }
+
+    /// Removes the rows in `rows`, moving the ones after them up
+    pub fn remove_rows(&mut self, rows: Range<usize>) {
+        assert!(rows.start <= rows.end && rows.end <= self.height, "rows {rows:?} aren't all in a grid {} tall", self.height);
+        self.cells.drain(rows.start * self.width..rows.end * self.width);
+        self.height -= rows.len();
+    }
+
+    /// Every position, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + 'static {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// Every cell with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.positions().zip(&self.cells)
+    }
+
+    /// The first position, row by row, whose cell matches `f`
+    pub fn position(&self, mut f: impl FnMut(&T) -> bool) -> Option<Pos> {
+        self.iter().find(|(_, cell)| f(cell)).map(|(pos, _)| pos)
+    }
+
+    /// A grid of what `f` gives for each cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { cells: self.cells.iter().map(f).collect(), height: self.height, width: self.width }
+    }
+
+    /// The grid flipped over its diagonal, so rows become columns
+    pub fn transposed(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.width, self.height, |(row, col)| self[(col, row)].clone())
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!("{pos:?} is outside a grid {} by {}", self.height, self.width),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (height, width) = (self.height, self.width);
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("{pos:?} is outside a grid {height} by {width}"),
+        }
+    }
+}
+
+/// Draws the grid a row to a line, with nothing between the cells
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, row) in self.rows().enumerate() {
+            if idx > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
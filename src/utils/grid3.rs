@@ -0,0 +1,169 @@
+//! A box of cells in three dimensions, for puzzles about voxels
+//!
+//! Positions are `(x, y, z)` triples, and the box can start anywhere, below zero included.
+//!
+//! ```
+//! use aoc::utils::Grid3;
+//!
+//! // A hollow cube with a single cell of air in the middle
+//! let mut cubes = Grid3::new((-1, -1, -1), (3, 3, 3), false);
+//! for pos in cubes.positions().filter(|&(x, y, z)| [x, y, z].iter().all(|n| (0..=2).contains(n))) {
+//!     cubes[pos] = pos != (1, 1, 1);
+//! }
+//! let outside = cubes.flood_fill_exterior(|&cube| !cube);
+//! assert!(outside[(-1, 0, 0)] && !outside[(1, 1, 1)]);
+//! assert_eq!(cubes.neighbors6((1, 1, 1)).filter(|&pos| cubes[pos]).count(), 6);
+//! ```
+
+use std::{collections::VecDeque, ops::{Index, IndexMut}};
+
+/// A cell's position, as its x, y and z
+pub type Pos3 = (isize, isize, isize);
+
+/// The steps to the six cells sharing a face with another
+pub const NEIGHBORS6: [Pos3; 6] = [(-1, 0, 0), (1, 0, 0), (0, -1, 0), (0, 1, 0), (0, 0, -1), (0, 0, 1)];
+
+/// The steps to the 26 cells touching another, by a face, an edge or a corner
+pub const NEIGHBORS26: [Pos3; 26] = {
+    let mut steps = [(0, 0, 0); 26];
+    let mut idx = 0;
+    let mut n = 0;
+    while n < 27 {
+        let step = (n / 9 - 1, n / 3 % 3 - 1, n % 3 - 1);
+        if n != 13 {
+            steps[idx] = step;
+            idx += 1;
+        }
+        n += 1;
+    }
+    steps
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid3<T> {
+    cells: Vec<T>,
+    min: Pos3,
+    /// How many cells the box spans along each axis
+    size: (usize, usize, usize),
+}
+
+impl<T> Grid3<T> {
+    /// A box from `min` to `max`, both included, with every cell set to `fill`
+    pub fn new(min: Pos3, max: Pos3, fill: T) -> Grid3<T>
+    where
+        T: Clone,
+    {
+        let span = |lo: isize, hi: isize| usize::try_from(hi - lo + 1).unwrap_or(0);
+        let size = (span(min.0, max.0), span(min.1, max.1), span(min.2, max.2));
+        Grid3 { cells: vec![fill; size.0 * size.1 * size.2], min, size }
+    }
+
+    /// The lowest and highest corners of the box, both included
+    pub fn bounds(&self) -> (Pos3, Pos3) {
+        let max = |lo: isize, len: usize| lo + len as isize - 1;
+        (self.min, (max(self.min.0, self.size.0), max(self.min.1, self.size.1), max(self.min.2, self.size.2)))
+    }
+
+    fn idx(&self, pos: Pos3) -> Option<usize> {
+        let offset = |n: isize, lo: isize, len: usize| usize::try_from(n - lo).ok().filter(|&off| off < len);
+        let x = offset(pos.0, self.min.0, self.size.0)?;
+        let y = offset(pos.1, self.min.1, self.size.1)?;
+        let z = offset(pos.2, self.min.2, self.size.2)?;
+        Some((x * self.size.1 + y) * self.size.2 + z)
+    }
+
+    pub fn get(&self, pos: Pos3) -> Option<&T> {
+        self.idx(pos).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, pos: Pos3) -> Option<&mut T> {
+        self.idx(pos).map(|idx| &mut self.cells[idx])
+    }
+
+    pub fn contains(&self, pos: Pos3) -> bool {
+        self.idx(pos).is_some()
+    }
+
+    /// The positions sharing a face with `pos`, leaving out any outside the box
+    pub fn neighbors6(&self, pos: Pos3) -> impl Iterator<Item = Pos3> + '_ {
+        self.neighbors(pos, &NEIGHBORS6)
+    }
+
+    /// The positions touching `pos` in any way, leaving out any outside the box
+    pub fn neighbors26(&self, pos: Pos3) -> impl Iterator<Item = Pos3> + '_ {
+        self.neighbors(pos, &NEIGHBORS26)
+    }
+
+    fn neighbors<'a>(&'a self, pos: Pos3, steps: &'static [Pos3]) -> impl Iterator<Item = Pos3> + 'a {
+        steps.iter()
+            .map(move |step| (pos.0 + step.0, pos.1 + step.1, pos.2 + step.2))
+            .filter(|&other| self.contains(other))
+    }
+
+    /// Every position in the box, by x, then y, then z
+    pub fn positions(&self) -> impl Iterator<Item = Pos3> + 'static {
+        let (min, size) = (self.min, self.size);
+        (0..size.0 as isize).flat_map(move |x| {
+            (0..size.1 as isize).flat_map(move |y| (0..size.2 as isize).map(move |z| (min.0 + x, min.1 + y, min.2 + z)))
+        })
+    }
+
+    /// Every cell with its position, by x, then y, then z
+    pub fn iter(&self) -> impl Iterator<Item = (Pos3, &T)> + '_ {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Every cell that can be reached from one of `seeds` by stepping between cells sharing a
+    /// face, only ever stepping onto cells `passable` allows
+    ///
+    /// Seeds outside the box or on cells that aren't passable are skipped.
+    pub fn flood_fill(&self, seeds: impl IntoIterator<Item = Pos3>, mut passable: impl FnMut(&T) -> bool) -> Grid3<bool> {
+        let mut reached = Grid3 { cells: vec![false; self.cells.len()], min: self.min, size: self.size };
+        let mut queue = VecDeque::new();
+        for seed in seeds {
+            if self.get(seed).is_some_and(&mut passable) && !reached[seed] {
+                reached[seed] = true;
+                queue.push_back(seed);
+            }
+        }
+        while let Some(pos) = queue.pop_front() {
+            for other in self.neighbors6(pos) {
+                if !reached[other] && passable(&self[other]) {
+                    reached[other] = true;
+                    queue.push_back(other);
+                }
+            }
+        }
+        reached
+    }
+
+    /// [`Grid3::flood_fill`] from every passable cell on the outside of the box
+    ///
+    /// Leave a layer of space around whatever's inside, so the fill can get all the way round.
+    pub fn flood_fill_exterior(&self, passable: impl FnMut(&T) -> bool) -> Grid3<bool> {
+        let (min, max) = self.bounds();
+        let on_face = move |&(x, y, z): &Pos3| x == min.0 || x == max.0 || y == min.1 || y == max.1 || z == min.2 || z == max.2;
+        self.flood_fill(self.positions().filter(on_face), passable)
+    }
+}
+
+impl<T> Index<Pos3> for Grid3<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos3) -> &T {
+        match self.get(pos) {
+            Some(cell) => cell,
+            None => panic!("{pos:?} is outside a box spanning {:?}", self.bounds()),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos3> for Grid3<T> {
+    fn index_mut(&mut self, pos: Pos3) -> &mut T {
+        let bounds = self.bounds();
+        match self.get_mut(pos) {
+            Some(cell) => cell,
+            None => panic!("{pos:?} is outside a box spanning {bounds:?}"),
+        }
+    }
+}
@@ -0,0 +1,258 @@
+//! Points and steps in two and three dimensions, for puzzles that move things about
+//!
+//! The coordinates can be any of the integer types the days use. Converting between them,
+//! say from the `isize` a sum gives to the `usize` a grid wants, is checked rather than cast.
+//!
+//! ```
+//! use aoc::utils::{Point2, Point3};
+//!
+//! let head = Point2::new(4, 2);
+//! let mut tail = Point2::new(2, 1);
+//! assert_eq!(head.manhattan(tail), 3);
+//! assert_eq!(head.chebyshev(tail), 2);
+//! tail = tail.step_toward(head);
+//! assert_eq!(tail, Point2::new(3, 2));
+//! assert_eq!(head - tail * 2, Point2::new(-2, -2));
+//!
+//! assert_eq!(Point2::new(-1isize, 3).try_cast::<usize>(), None);
+//! assert_eq!(Point2::new(0usize, 0).neighbors4().count(), 2);
+//! assert_eq!(Point3::new(1, 1, 1).neighbors26().count(), 26);
+//! ```
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use super::{grid::{NEIGHBORS4, NEIGHBORS8}, grid3::{NEIGHBORS26, NEIGHBORS6}};
+
+/// An integer type a point's coordinates can be
+pub trait Coord: Copy + Ord + Add<Output = Self> + Sub<Output = Self> {
+    const ZERO: Self;
+    const ONE: Self;
+
+    /// How far apart two coordinates are, whichever is bigger
+    fn distance(self, other: Self) -> usize;
+
+    /// The coordinate `step` away, if the type can hold it
+    fn offset(self, step: isize) -> Option<Self>;
+}
+
+macro_rules! coords {
+    ($($ty:ty),*) => {$(
+        impl Coord for $ty {
+            const ZERO: $ty = 0;
+            const ONE: $ty = 1;
+
+            fn distance(self, other: $ty) -> usize {
+                self.abs_diff(other) as usize
+            }
+
+            fn offset(self, step: isize) -> Option<$ty> {
+                <$ty>::try_from(i128::try_from(self).ok()? + step as i128).ok()
+            }
+        }
+    )*};
+}
+
+coords!(i32, i64, isize, u32, u64, usize);
+
+/// One step from `from` toward `to`, or `from` itself if they're the same
+fn step<T: Coord>(from: T, to: T) -> T {
+    match to.cmp(&from) {
+        std::cmp::Ordering::Less => from - T::ONE,
+        std::cmp::Ordering::Equal => from,
+        std::cmp::Ordering::Greater => from + T::ONE,
+    }
+}
+
+/// Which way `n` points from zero, as -1, 0 or 1
+fn signum<T: Coord + Neg<Output = T>>(n: T) -> T {
+    step(T::ZERO, n)
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2<T> {
+    pub const fn new(x: T, y: T) -> Point2<T> {
+        Point2 { x, y }
+    }
+}
+
+impl<T: Coord> Point2<T> {
+    /// The distance along the axes, stepping only up, down, left or right
+    pub fn manhattan(self, other: Point2<T>) -> usize {
+        self.x.distance(other.x) + self.y.distance(other.y)
+    }
+
+    /// The distance when diagonal steps count as one, like a king's moves
+    pub fn chebyshev(self, other: Point2<T>) -> usize {
+        self.x.distance(other.x).max(self.y.distance(other.y))
+    }
+
+    /// The point one step from this one toward `target`, diagonally if it's off both axes
+    pub fn step_toward(self, target: Point2<T>) -> Point2<T> {
+        Point2::new(step(self.x, target.x), step(self.y, target.y))
+    }
+
+    /// The point `step` away, if the coordinate type can hold it
+    pub fn offset(self, step: (isize, isize)) -> Option<Point2<T>> {
+        Some(Point2::new(self.x.offset(step.0)?, self.y.offset(step.1)?))
+    }
+
+    /// The points sharing a side with this one, leaving out any the type can't hold
+    pub fn neighbors4(self) -> impl Iterator<Item = Point2<T>> {
+        NEIGHBORS4.into_iter().filter_map(move |step| self.offset(step))
+    }
+
+    /// The points touching this one, diagonals included, leaving out any the type can't hold
+    pub fn neighbors8(self) -> impl Iterator<Item = Point2<T>> {
+        NEIGHBORS8.into_iter().filter_map(move |step| self.offset(step))
+    }
+
+    /// The same point with another type of coordinate, if both of them fit in it
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Point2<U>> {
+        Some(Point2::new(U::try_from(self.x).ok()?, U::try_from(self.y).ok()?))
+    }
+}
+
+impl<T: Coord + Neg<Output = T>> Point2<T> {
+    /// Which way the point is from the origin on each axis, as -1, 0 or 1
+    pub fn signum(self) -> Point2<T> {
+        Point2::new(signum(self.x), signum(self.y))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Point3<T> {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T: Coord> Point3<T> {
+    /// The distance along the axes, never stepping diagonally
+    pub fn manhattan(self, other: Point3<T>) -> usize {
+        self.x.distance(other.x) + self.y.distance(other.y) + self.z.distance(other.z)
+    }
+
+    /// The distance when any diagonal step counts as one
+    pub fn chebyshev(self, other: Point3<T>) -> usize {
+        self.x.distance(other.x).max(self.y.distance(other.y)).max(self.z.distance(other.z))
+    }
+
+    /// The point one step from this one toward `target`, diagonally if it's off the axes
+    pub fn step_toward(self, target: Point3<T>) -> Point3<T> {
+        Point3::new(step(self.x, target.x), step(self.y, target.y), step(self.z, target.z))
+    }
+
+    /// The point `step` away, if the coordinate type can hold it
+    pub fn offset(self, step: (isize, isize, isize)) -> Option<Point3<T>> {
+        Some(Point3::new(self.x.offset(step.0)?, self.y.offset(step.1)?, self.z.offset(step.2)?))
+    }
+
+    /// The points sharing a face with this one, leaving out any the type can't hold
+    pub fn neighbors6(self) -> impl Iterator<Item = Point3<T>> {
+        NEIGHBORS6.into_iter().filter_map(move |step| self.offset(step))
+    }
+
+    /// The points touching this one in any way, leaving out any the type can't hold
+    pub fn neighbors26(self) -> impl Iterator<Item = Point3<T>> {
+        NEIGHBORS26.into_iter().filter_map(move |step| self.offset(step))
+    }
+
+    /// The same point with another type of coordinate, if all of them fit in it
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Point3<U>> {
+        Some(Point3::new(U::try_from(self.x).ok()?, U::try_from(self.y).ok()?, U::try_from(self.z).ok()?))
+    }
+}
+
+impl<T: Coord + Neg<Output = T>> Point3<T> {
+    /// Which way the point is from the origin on each axis, as -1, 0 or 1
+    pub fn signum(self) -> Point3<T> {
+        Point3::new(signum(self.x), signum(self.y), signum(self.z))
+    }
+}
+
+// The arithmetic is done a coordinate at a time, with a number scaling every coordinate
+macro_rules! ops {
+    ($point:ident { $($field:ident),* }) => {
+        impl<T: Add<Output = T>> Add for $point<T> {
+            type Output = $point<T>;
+
+            fn add(self, other: $point<T>) -> $point<T> {
+                $point { $($field: self.$field + other.$field),* }
+            }
+        }
+
+        impl<T: Sub<Output = T>> Sub for $point<T> {
+            type Output = $point<T>;
+
+            fn sub(self, other: $point<T>) -> $point<T> {
+                $point { $($field: self.$field - other.$field),* }
+            }
+        }
+
+        impl<T: Copy + Mul<Output = T>> Mul<T> for $point<T> {
+            type Output = $point<T>;
+
+            fn mul(self, n: T) -> $point<T> {
+                $point { $($field: self.$field * n),* }
+            }
+        }
+
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = $point<T>;
+
+            fn neg(self) -> $point<T> {
+                $point { $($field: -self.$field),* }
+            }
+        }
+
+        impl<T: AddAssign> AddAssign for $point<T> {
+            fn add_assign(&mut self, other: $point<T>) {
+                $(self.$field += other.$field;)*
+            }
+        }
+
+        impl<T: SubAssign> SubAssign for $point<T> {
+            fn sub_assign(&mut self, other: $point<T>) {
+                $(self.$field -= other.$field;)*
+            }
+        }
+    };
+}
+
+ops!(Point2 { x, y });
+ops!(Point3 { x, y, z });
+
+impl<T> From<(T, T)> for Point2<T> {
+    fn from((x, y): (T, T)) -> Point2<T> {
+        Point2 { x, y }
+    }
+}
+
+impl<T> From<Point2<T>> for (T, T) {
+    fn from(point: Point2<T>) -> (T, T) {
+        (point.x, point.y)
+    }
+}
+
+impl<T> From<(T, T, T)> for Point3<T> {
+    fn from((x, y, z): (T, T, T)) -> Point3<T> {
+        Point3 { x, y, z }
+    }
+}
+
+impl<T> From<Point3<T>> for (T, T, T) {
+    fn from(point: Point3<T>) -> (T, T, T) {
+        (point.x, point.y, point.z)
+    }
+}
@@ -0,0 +1,153 @@
+//! A grid with no edges, for puzzles whose area grows as they run
+//!
+//! Positions are `(row, column)` pairs that can go negative. Only the cells that have been set
+//! are stored, so looking at an empty one costs nothing.
+//!
+//! ```
+//! use aoc::utils::SparseGrid;
+//!
+//! let mut elves = SparseGrid::new();
+//! elves.insert((0, 0), '#');
+//! elves.insert((-2, 3), '#');
+//! assert_eq!(elves.get((-1, 1)), None);
+//! assert_eq!(elves.len(), 2);
+//! assert_eq!(elves.bounds(), Some(((-2, 0), (0, 3))));
+//! assert_eq!(elves.render(|cell| cell.copied().unwrap_or('.')), "...#\n....\n#...");
+//! ```
+
+use std::{collections::{BTreeMap, HashMap}, fmt::{self, Display}};
+
+/// A cell's position, as its row then its column
+pub type Pos = (isize, isize);
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Pos, T>,
+    /// How many cells are set in each row and column that has any, so the bounds can be
+    /// kept up to date as cells come and go
+    rows: BTreeMap<isize, usize>,
+    cols: BTreeMap<isize, usize>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> SparseGrid<T> {
+        SparseGrid { cells: HashMap::new(), rows: BTreeMap::new(), cols: BTreeMap::new() }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    /// Sets the cell at `pos`, giving back what was there before
+    pub fn insert(&mut self, pos: Pos, value: T) -> Option<T> {
+        let old = self.cells.insert(pos, value);
+        if old.is_none() {
+            *self.rows.entry(pos.0).or_default() += 1;
+            *self.cols.entry(pos.1).or_default() += 1;
+        }
+        old
+    }
+
+    /// Empties the cell at `pos`, giving back what was there
+    pub fn remove(&mut self, pos: Pos) -> Option<T> {
+        let old = self.cells.remove(&pos)?;
+        forget(&mut self.rows, pos.0);
+        forget(&mut self.cols, pos.1);
+        Some(old)
+    }
+
+    /// The top left and bottom right corners of the smallest rectangle holding every cell
+    /// that's set, or `None` when none are
+    pub fn bounds(&self) -> Option<(Pos, Pos)> {
+        let (&top, _) = self.rows.first_key_value()?;
+        let (&bottom, _) = self.rows.last_key_value()?;
+        let (&left, _) = self.cols.first_key_value()?;
+        let (&right, _) = self.cols.last_key_value()?;
+        Some(((top, left), (bottom, right)))
+    }
+
+    /// The cells that are set, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.cells.iter().map(|(&pos, value)| (pos, value))
+    }
+
+    /// The positions of the cells that are set, in no particular order
+    pub fn positions(&self) -> impl Iterator<Item = Pos> + '_ {
+        self.cells.keys().copied()
+    }
+
+    /// Draws the bounds a row to a line, with `cell` giving the character for each cell,
+    /// given `None` for the empty ones
+    pub fn render(&self, mut cell: impl FnMut(Option<&T>) -> char) -> String {
+        let Some(((top, left), (bottom, right))) = self.bounds() else { return String::new() };
+        let mut text = String::new();
+        for row in top..=bottom {
+            if row > top {
+                text.push('\n');
+            }
+            text.extend((left..=right).map(|col| cell(self.get((row, col)))));
+        }
+        text
+    }
+}
+
+/// Takes a cell off the count for its row or column
+fn forget(counts: &mut BTreeMap<isize, usize>, line: isize) {
+    if let Some(count) = counts.get_mut(&line) {
+        *count -= 1;
+        if *count == 0 {
+            counts.remove(&line);
+        }
+    }
+}
+
+impl<T> FromIterator<(Pos, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Pos, T)>>(cells: I) -> SparseGrid<T> {
+        let mut grid = SparseGrid::new();
+        for (pos, value) in cells {
+            grid.insert(pos, value);
+        }
+        grid
+    }
+}
+
+/// Draws the bounds a row to a line, with `.` for the empty cells
+impl<T: Display> Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(((top, left), (bottom, right))) = self.bounds() else { return Ok(()) };
+        for row in top..=bottom {
+            if row > top {
+                writeln!(f)?;
+            }
+            for col in left..=right {
+                match self.get((row, col)) {
+                    Some(cell) => write!(f, "{cell}")?,
+                    None => write!(f, ".")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
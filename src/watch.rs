@@ -0,0 +1,186 @@
+use std::{env, fs, io::{self, IsTerminal}, os::unix::process::CommandExt, path::{Path, PathBuf}, process::Command, thread, time::{Duration, SystemTime}};
+
+use colored::Colorize;
+
+use crate::{answers::Verdict, fixtures::Fixture, input::Inputs, params::Tuned, runner::{self, PartResult}, solution::Solution};
+
+/// How often the watched files are checked for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Carries the last results over when the watcher restarts itself after a rebuild, so the
+/// first run of the new code is still compared with the old
+const PREVIOUS_RESULTS_VAR: &str = "AOC_WATCH_PREVIOUS";
+
+/// The files that trigger a re-run, and what they looked like last time
+struct Watched {
+    source: PathBuf,
+    data: Vec<PathBuf>,
+    source_stamp: Vec<(PathBuf, SystemTime)>,
+    data_stamp: Vec<Option<SystemTime>>,
+}
+
+/// What changed since the files were last looked at
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Change {
+    Source,
+    Data,
+}
+
+impl Watched {
+    fn new(source: PathBuf, data: Vec<PathBuf>) -> Watched {
+        let mut watched = Watched { source, data, source_stamp: Vec::new(), data_stamp: Vec::new() };
+        watched.poll();
+        watched
+    }
+
+    /// Takes a fresh look at the files, saying what changed since the last one
+    fn poll(&mut self) -> Option<Change> {
+        let mut source_stamp = Vec::new();
+        modified_under(&self.source, &mut source_stamp);
+        source_stamp.sort();
+        let data_stamp = self.data.iter().map(|path| fs::metadata(path).and_then(|m| m.modified()).ok()).collect::<Vec<_>>();
+
+        let change = if source_stamp != self.source_stamp {
+            Some(Change::Source)
+        } else if data_stamp != self.data_stamp {
+            Some(Change::Data)
+        } else {
+            None
+        };
+        self.source_stamp = source_stamp;
+        self.data_stamp = data_stamp;
+        change
+    }
+
+    fn wait(&mut self) -> Change {
+        loop {
+            thread::sleep(POLL_INTERVAL);
+            if let Some(change) = self.poll() {
+                // Editors often write a file in more than one go, so let them finish
+                thread::sleep(POLL_INTERVAL);
+                self.poll();
+                return change;
+            }
+        }
+    }
+}
+
+/// Every `.rs` file under `dir` and when it was last modified
+fn modified_under(dir: &Path, stamps: &mut Vec<(PathBuf, SystemTime)>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else { continue };
+        if metadata.is_dir() {
+            modified_under(&path, stamps);
+        } else if let Some(modified) = metadata.modified().ok().filter(|_| path.extension().is_some_and(|ext| ext == "rs")) {
+            stamps.push((path, modified));
+        }
+    }
+}
+
+/// Runs a day whenever its input, example fixture or the source changes, comparing each run
+/// with the one before
+///
+/// Input and fixture changes re-run the day straight away. Source changes rebuild with cargo
+/// first, then restart the watcher on the new binary. Never returns unless restarting fails.
+pub fn watch(solution: &'static dyn Solution, inputs: &Inputs, limit: Option<Duration>) -> io::Result<()> {
+    // Taken now, since rebuilding replaces the file and Linux then reports it as deleted
+    let exe = env::current_exe()?;
+    let (year, day) = (solution.year(), solution.day());
+    let mut watched = Watched::new(PathBuf::from("src"), vec![inputs.path(year, day), Fixture::path(year, day)]);
+    let mut previous = env::var(PREVIOUS_RESULTS_VAR).ok().and_then(|json| serde_json::from_str::<Vec<PartResult>>(&json).ok());
+
+    loop {
+        if io::stdout().is_terminal() {
+            // Clears the screen, so the latest run is always at the top
+            print!("\x1b[2J\x1b[H");
+        }
+        let results = match inputs.read(year, day) {
+            Ok(input) => {
+                let results = runner::solve(solution, &input, None, limit);
+                runner::print(solution, &results);
+                if let Some(previous) = &previous {
+                    print_changes(previous, &results);
+                }
+                Some(results)
+            }
+            Err(e) => {
+                println!("{} {e}", "error:".red());
+                None
+            }
+        };
+        check_examples(solution, limit);
+        previous = results.or(previous);
+        println!("{}", format!("Watching src, {} and {}", watched.data[0].display(), watched.data[1].display()).dimmed());
+
+        while watched.wait() == Change::Source {
+            println!("{}", "Rebuilding...".dimmed());
+            if rebuild()? {
+                let mut restart = Command::new(&exe);
+                restart.args(env::args_os().skip(1));
+                if let Some(previous) = &previous {
+                    restart.env(PREVIOUS_RESULTS_VAR, serde_json::to_string(previous)?);
+                }
+                // Only comes back if the new binary couldn't be started
+                return Err(restart.exec());
+            }
+            println!("{}", "The build failed, waiting for another change".red());
+        }
+    }
+}
+
+/// Builds the binary the way the running one was built, saying whether it worked
+fn rebuild() -> io::Result<bool> {
+    let mut cargo = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()));
+    cargo.args(["build", "--bin", env!("CARGO_PKG_NAME")]);
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+    Ok(cargo.status()?.success())
+}
+
+/// Says how each part's answer and time changed since the previous run
+fn print_changes(previous: &[PartResult], results: &[PartResult]) {
+    for result in results {
+        let Some(before) = previous.iter().find(|before| before.part == result.part) else { continue };
+        match (&before.answer, &result.answer) {
+            (Ok(old), Ok(new)) if old != new => {
+                println!("Part {} answer changed from {} to {}", result.part, old.one_line().yellow(), new.one_line().yellow());
+            }
+            (Err(_), Ok(_)) => println!("Part {} {}", result.part, "works again".green()),
+            (Ok(_), Err(_)) => println!("Part {} {}", result.part, "stopped working".red()),
+            _ => (),
+        }
+        if before.answer.is_ok() && result.answer.is_ok() && !before.elapsed.is_zero() {
+            let percent = (result.elapsed.as_secs_f64() / before.elapsed.as_secs_f64() - 1.0) * 100.0;
+            let change = format!("{percent:+.1}%");
+            let change = if percent < 0.0 { change.green() } else { change.red() };
+            println!("Part {} took {change} ({:?} before)", result.part, before.elapsed);
+        }
+    }
+}
+
+/// Runs the day on its example fixture, if it has one, checking the answers it gives
+fn check_examples(solution: &'static dyn Solution, limit: Option<Duration>) {
+    let fixture = match Fixture::load(solution.year(), solution.day()) {
+        Ok(fixture) => fixture,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return,
+        Err(e) => {
+            println!("{} {e}", "error:".red());
+            return;
+        }
+    };
+    let solution = Tuned::leak(solution, fixture.params);
+    for result in runner::solve(solution, &fixture.input, None, limit) {
+        let Some(expected) = fixture.expected.get(&result.part) else { continue };
+        let verdict = Verdict::check(Some(expected), &result.answer);
+        match &result.answer {
+            Ok(answer) if verdict == Verdict::Mismatch => {
+                println!("{} example part {}: expected {}, got {}", verdict.colored(), result.part, expected.one_line(), answer.one_line());
+            }
+            Err(e) => println!("{} example part {}: {e}", verdict.colored(), result.part),
+            Ok(_) => println!("{} example part {}", verdict.colored(), result.part),
+        }
+    }
+}
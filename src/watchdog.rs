@@ -0,0 +1,80 @@
+use std::{collections::BTreeMap, panic, sync::{mpsc::{self, RecvTimeoutError}, Arc}, thread, time::{Duration, Instant}};
+
+use crate::{error::{AocError, Result}, runner::{self, PartResult}, solution::{Parsed, Part, Solution}};
+
+/// Parts get as much stack as the main thread has on Linux, which the deeper searches rely on
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// How long each part may run before it's given up on
+///
+/// There's a default for every day, which single days can override. Parts run without a limit
+/// when neither is set.
+#[derive(Clone, Debug, Default)]
+pub struct Timeouts {
+    default: Option<Duration>,
+    overrides: BTreeMap<u32, Duration>,
+}
+
+impl Timeouts {
+    pub fn new(default: Option<Duration>) -> Timeouts {
+        Timeouts { default, overrides: BTreeMap::new() }
+    }
+
+    /// Gives `day` its own limit, in every year
+    pub fn with_override(mut self, day: u32, limit: Duration) -> Timeouts {
+        self.overrides.insert(day, limit);
+        self
+    }
+
+    pub fn limit(&self, day: u32) -> Option<Duration> {
+        self.overrides.get(&day).copied().or(self.default)
+    }
+}
+
+/// Runs and times a single part on its own thread, giving up once `limit` has passed
+///
+/// Threads can't be stopped from outside, so a part that runs over keeps going in the
+/// background until the process exits.
+pub fn time_within(solution: &'static dyn Solution, part: Part, parsed: Arc<Parsed>, limit: Duration) -> PartResult {
+    let start = Instant::now();
+    let name = format!("{} day {} part {part}", solution.year(), solution.day());
+    within(name, limit, move || runner::time(solution, part, &parsed)).unwrap_or_else(|| PartResult {
+        part,
+        answer: Err(AocError::Timeout { day: solution.day(), limit }),
+        elapsed: Instant::now().duration_since(start),
+        parse: None,
+        alloc: None,
+    })
+}
+
+/// Parses and times a day's input on its own thread, giving up once `limit` has passed
+pub fn parse_within(solution: &'static dyn Solution, input: Arc<str>, limit: Duration) -> (Result<Parsed>, Duration) {
+    let start = Instant::now();
+    let name = format!("{} day {} parser", solution.year(), solution.day());
+    within(name, limit, move || runner::parse(solution, &input)).unwrap_or_else(|| {
+        (Err(AocError::Timeout { day: solution.day(), limit }), Instant::now().duration_since(start))
+    })
+}
+
+/// Runs `f` on a thread called `name`, returning what it gives back unless `limit` passes first
+fn within<T: Send + 'static>(name: String, limit: Duration, f: impl FnOnce() -> T + Send + 'static) -> Option<T> {
+    let (sender, receiver) = mpsc::channel();
+    let handle = thread::Builder::new()
+        .name(name)
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            // Nobody is listening any more if it has already timed out
+            let _ = sender.send(f());
+        })
+        .expect("couldn't start a thread for the part");
+
+    match receiver.recv_timeout(limit) {
+        Ok(result) => Some(result),
+        Err(RecvTimeoutError::Timeout) => None,
+        // It panicked before sending anything, so pass the panic on as if it had run here
+        Err(RecvTimeoutError::Disconnected) => match handle.join() {
+            Err(payload) => panic::resume_unwind(payload),
+            Ok(()) => unreachable!("the thread finished without sending its result"),
+        },
+    }
+}
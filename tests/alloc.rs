@@ -0,0 +1,22 @@
+//! The allocation counts only work with [`CountingAlloc`] as the global allocator, so this
+//! test gets a binary of its own rather than slowing every unit test down with it
+
+use aoc::alloc::{self, AllocStats, CountingAlloc};
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+#[test]
+fn allocations_are_counted() {
+    alloc::enable();
+    let (_, stats) = alloc::track(|| {
+        let small = vec![0u8; 100];
+        drop(small);
+        let mut grown = Vec::<u8>::with_capacity(1000);
+        grown.reserve_exact(2000);
+        grown.len()
+    });
+    // The reservation is a realloc, which counts as a second allocation
+    assert_eq!(stats, Some(AllocStats { count: 3, bytes: 3100, peak: 2000 }));
+    assert_eq!(stats.unwrap().to_string(), "3 allocations, 3.0 KiB total, 2.0 KiB peak");
+}